@@ -1,8 +1,10 @@
 use crate::empty_db_wrapper::EmptyDBWrapper;
+use crate::python_db::PythonDB;
+use crate::types::AccountOverride;
 use crate::utils::pyerr;
 use ethers_core::types::BlockId;
 use ethers_providers::{Http, Provider};
-use pyo3::{PyErr, PyResult};
+use pyo3::{PyErr, PyObject, PyResult};
 use revm::db::{CacheDB, DbAccount, EthersDB};
 use revm::precompile::{Address, B256};
 use revm::primitives::{AccountInfo, Bytecode, HashMap, State};
@@ -15,12 +17,46 @@ use std::sync::Arc;
 type MemDB = CacheDB<EmptyDBWrapper>;
 type ForkDB = CacheDB<EthersDB<Provider<Http>>>;
 
+/// Accounts/slots fetched concurrently per batch in `DB::prefetch`, mirroring Helios's
+/// `PARALLEL_QUERY_BATCH_SIZE` approach so we don't open unbounded simultaneous RPC connections.
+const PARALLEL_QUERY_BATCH_SIZE: usize = 20;
+
+/// A per-account override consulted by `DB::Overlay` instead of (or in addition to) the real
+/// backing database.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct StateOverride {
+    pub(crate) balance: Option<U256>,
+    pub(crate) nonce: Option<u64>,
+    pub(crate) code: Option<Bytecode>,
+    pub(crate) storage: HashMap<U256, U256>,
+    pub(crate) storage_reset: bool,
+}
+
+impl From<AccountOverride> for StateOverride {
+    fn from(over: AccountOverride) -> Self {
+        StateOverride {
+            balance: over.balance,
+            nonce: over.nonce,
+            code: over.code.map(|bytes| Bytecode::new_raw(bytes.into())),
+            storage: over.storage,
+            storage_reset: over.storage_reset,
+        }
+    }
+}
+
 /// A wrapper around the `CacheDB` and `EthersDB` to provide a common interface
 /// without needing dynamic lifetime and generic parameters (unsupported in PyO3)
 #[derive(Clone, Debug)]
 pub(crate) enum DB {
     Memory(MemDB),
     Fork(ForkDB),
+    /// A user-supplied Python object implementing `basic`/`code_by_hash`/`storage`/`block_hash`,
+    /// for plugging in a custom state source (a bespoke RPC, a local snapshot service, a test
+    /// fixture).
+    Python(PythonDB),
+    /// A temporary overlay layering per-account overrides on top of another `DB`, used for the
+    /// duration of a single `eth_call`-style override call. Never committed to.
+    Overlay(Box<DB>, HashMap<Address, StateOverride>),
 }
 
 impl DB {
@@ -39,11 +75,32 @@ impl DB {
         Ok(DB::Fork(CacheDB::new(db)))
     }
 
+    pub(crate) fn new_python(provider: PyObject) -> Self {
+        DB::Python(PythonDB::new(provider))
+    }
+
+    /// Wraps `self` in a temporary overlay consulting `overrides` before falling through to the
+    /// real database. Use `into_inner` to discard the overlay again once the call is done.
+    pub(crate) fn with_overrides(self, overrides: HashMap<Address, StateOverride>) -> Self {
+        DB::Overlay(Box::new(self), overrides)
+    }
+
+    /// Unwraps an overlay back to the database it was layered on top of. A no-op for non-overlay
+    /// variants.
+    pub(crate) fn into_inner(self) -> Self {
+        match self {
+            DB::Overlay(inner, _) => *inner,
+            other => other,
+        }
+    }
+
     /// Insert account info but not override storage
     pub(crate) fn insert_account_info(&mut self, address: Address, info: AccountInfo) {
         match self {
             DB::Memory(db) => db.insert_account_info(address, info),
             DB::Fork(db) => db.insert_account_info(address, info),
+            DB::Python(_) => {}
+            DB::Overlay(db, _) => db.insert_account_info(address, info),
         }
     }
 
@@ -51,8 +108,87 @@ impl DB {
         match self {
             DB::Memory(db) => &db.accounts,
             DB::Fork(db) => &db.accounts,
+            DB::Python(_) => {
+                // A Python-backed provider has no enumerable local cache.
+                static EMPTY: std::sync::OnceLock<HashMap<Address, DbAccount>> =
+                    std::sync::OnceLock::new();
+                EMPTY.get_or_init(HashMap::new)
+            }
+            DB::Overlay(db, _) => db.get_accounts(),
         }
     }
+
+    /// Inserts a single storage slot for `address`, used to rehydrate a dumped snapshot.
+    pub(crate) fn insert_account_storage(
+        &mut self,
+        address: Address,
+        slot: U256,
+        value: U256,
+    ) -> PyResult<()> {
+        match self {
+            DB::Memory(db) => db.insert_account_storage(address, slot, value).map_err(pyerr),
+            DB::Fork(db) => db.insert_account_storage(address, slot, value).map_err(pyerr),
+            DB::Python(_) => Ok(()),
+            DB::Overlay(db, _) => db.insert_account_storage(address, slot, value),
+        }
+    }
+
+    /// For a fork-backed `DB`, concurrently fetches account info for `addresses` and the given
+    /// `slots`, populating the in-memory cache so a subsequent call runs entirely against warm
+    /// local state instead of one blocking RPC round trip per access. A no-op for every other
+    /// `DB` variant.
+    pub(crate) fn prefetch(
+        &mut self,
+        addresses: Vec<Address>,
+        slots: Vec<(Address, U256)>,
+    ) -> PyResult<()> {
+        let DB::Fork(fork) = self else {
+            return Ok(());
+        };
+
+        for batch in addresses.chunks(PARALLEL_QUERY_BATCH_SIZE) {
+            let results: Vec<(Address, _)> = std::thread::scope(|scope| {
+                batch
+                    .iter()
+                    .map(|address| {
+                        let address = *address;
+                        let backend = &fork.db;
+                        scope.spawn(move || (address, backend.basic_ref(address)))
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .map(|handle| handle.join().expect("prefetch worker panicked"))
+                    .collect()
+            });
+            for (address, info) in results {
+                if let Some(info) = info.map_err(pyerr)? {
+                    fork.insert_account_info(address, info);
+                }
+            }
+        }
+
+        for batch in slots.chunks(PARALLEL_QUERY_BATCH_SIZE) {
+            let results: Vec<(Address, U256, _)> = std::thread::scope(|scope| {
+                batch
+                    .iter()
+                    .map(|(address, index)| {
+                        let (address, index) = (*address, *index);
+                        let backend = &fork.db;
+                        scope.spawn(move || (address, index, backend.storage_ref(address, index)))
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .map(|handle| handle.join().expect("prefetch worker panicked"))
+                    .collect()
+            });
+            for (address, index, value) in results {
+                fork.insert_account_storage(address, index, value.map_err(pyerr)?)
+                    .map_err(pyerr)?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl Database for DB {
@@ -62,6 +198,11 @@ impl Database for DB {
         Ok(match self {
             DB::Memory(db) => db.basic(address).map_err(pyerr)?,
             DB::Fork(db) => db.basic(address).map_err(pyerr)?,
+            DB::Python(db) => db.basic(address)?,
+            DB::Overlay(db, overrides) => {
+                let info = db.basic(address)?;
+                apply_account_override(info, overrides.get(&address))
+            }
         })
     }
 
@@ -69,6 +210,8 @@ impl Database for DB {
         Ok(match self {
             DB::Memory(db) => db.code_by_hash(code_hash).map_err(pyerr)?,
             DB::Fork(db) => db.code_by_hash(code_hash).map_err(pyerr)?,
+            DB::Python(db) => db.code_by_hash(code_hash)?,
+            DB::Overlay(db, _) => db.code_by_hash(code_hash)?,
         })
     }
 
@@ -76,6 +219,11 @@ impl Database for DB {
         Ok(match self {
             DB::Memory(db) => db.storage(address, index).map_err(pyerr)?,
             DB::Fork(db) => db.storage(address, index).map_err(pyerr)?,
+            DB::Python(db) => db.storage(address, index)?,
+            DB::Overlay(db, overrides) => match overrides.get(&address) {
+                Some(over) => apply_storage_override(over, index, || db.storage(address, index))?,
+                None => db.storage(address, index)?,
+            },
         })
     }
 
@@ -83,6 +231,8 @@ impl Database for DB {
         Ok(match self {
             DB::Memory(db) => db.block_hash(number).map_err(pyerr)?,
             DB::Fork(db) => db.block_hash(number).map_err(pyerr)?,
+            DB::Python(db) => db.block_hash(number)?,
+            DB::Overlay(db, _) => db.block_hash(number)?,
         })
     }
 }
@@ -92,6 +242,8 @@ impl DatabaseCommit for DB {
         match self {
             DB::Memory(ref mut db) => db.commit(changes),
             DB::Fork(ref mut db) => db.commit(changes),
+            DB::Python(_) => {}
+            DB::Overlay(ref mut db, _) => db.commit(changes),
         }
     }
 }
@@ -103,6 +255,11 @@ impl DatabaseRef for DB {
         Ok(match self {
             DB::Memory(db) => db.basic_ref(address).map_err(pyerr)?,
             DB::Fork(db) => db.basic_ref(address).map_err(pyerr)?,
+            DB::Python(db) => db.basic(address)?,
+            DB::Overlay(db, overrides) => {
+                let info = db.basic_ref(address)?;
+                apply_account_override(info, overrides.get(&address))
+            }
         })
     }
 
@@ -110,6 +267,8 @@ impl DatabaseRef for DB {
         Ok(match self {
             DB::Memory(db) => db.code_by_hash_ref(code_hash).map_err(pyerr)?,
             DB::Fork(db) => db.code_by_hash_ref(code_hash).map_err(pyerr)?,
+            DB::Python(db) => db.code_by_hash(code_hash)?,
+            DB::Overlay(db, _) => db.code_by_hash_ref(code_hash)?,
         })
     }
 
@@ -117,6 +276,13 @@ impl DatabaseRef for DB {
         Ok(match self {
             DB::Memory(db) => db.storage_ref(address, index).map_err(pyerr)?,
             DB::Fork(db) => db.storage_ref(address, index).map_err(pyerr)?,
+            DB::Python(db) => db.storage(address, index)?,
+            DB::Overlay(db, overrides) => match overrides.get(&address) {
+                Some(over) => {
+                    apply_storage_override(over, index, || db.storage_ref(address, index))?
+                }
+                None => db.storage_ref(address, index)?,
+            },
         })
     }
 
@@ -124,6 +290,44 @@ impl DatabaseRef for DB {
         Ok(match self {
             DB::Memory(db) => db.block_hash_ref(number).map_err(pyerr)?,
             DB::Fork(db) => db.block_hash_ref(number).map_err(pyerr)?,
+            DB::Python(db) => db.block_hash(number)?,
+            DB::Overlay(db, _) => db.block_hash_ref(number)?,
         })
     }
 }
+
+/// Layers a [`StateOverride`] on top of the account info the real database returned.
+fn apply_account_override(
+    info: Option<AccountInfo>,
+    over: Option<&StateOverride>,
+) -> Option<AccountInfo> {
+    let Some(over) = over else { return info };
+    let mut info = info.unwrap_or_default();
+    if let Some(balance) = over.balance {
+        info.balance = balance;
+    }
+    if let Some(nonce) = over.nonce {
+        info.nonce = nonce;
+    }
+    if let Some(code) = &over.code {
+        info.code_hash = code.hash_slow();
+        info.code = Some(code.clone());
+    }
+    Some(info)
+}
+
+/// Resolves a storage slot read through a [`StateOverride`], falling back to `real` only when
+/// the override doesn't fully reset storage and doesn't name this slot.
+fn apply_storage_override(
+    over: &StateOverride,
+    index: U256,
+    real: impl FnOnce() -> Result<U256, PyErr>,
+) -> Result<U256, PyErr> {
+    if let Some(value) = over.storage.get(&index) {
+        return Ok(*value);
+    }
+    if over.storage_reset {
+        return Ok(U256::ZERO);
+    }
+    real()
+}
@@ -8,17 +8,24 @@ use pyo3::{pyclass, pymethods, PyObject, PyResult, Python};
 use revm::precompile::{Address, Bytes};
 use revm::primitives::ExecutionResult::Success;
 use revm::primitives::{
-    BlockEnv as RevmBlockEnv, CreateScheme, Env as RevmEnv, ExecutionResult as RevmExecutionResult,
-    HandlerCfg, Output, SpecId, TransactTo, TxEnv as RevmTxEnv,
+    Account, BlockEnv as RevmBlockEnv, CreateScheme, Env as RevmEnv,
+    ExecutionResult as RevmExecutionResult, HandlerCfg, Output, SpecId, TransactTo,
+    TxEnv as RevmTxEnv,
+};
+use revm::{
+    primitives::U256, Evm, EvmContext, JournalCheckpoint as RevmCheckpoint, JournalEntry,
 };
-use revm::{primitives::U256, Evm, EvmContext, JournalCheckpoint as RevmCheckpoint};
 use tracing::trace;
 
 use crate::database::DB;
-use crate::executor::call_evm;
+use crate::executor::{call_evm, CustomPrecompiles};
+use crate::exceptions::RevertError;
 use crate::types::{PyByteVec, PyDB};
 use crate::{
-    types::{AccountInfo, BlockEnv, Env, ExecutionResult, JournalCheckpoint, TxEnv},
+    types::{
+        AccountDiff, AccountInfo, AccountOverride, BlockEnv, CallFrame, Env, ExecutionResult,
+        JournalCheckpoint, TraceStep, TxEnv,
+    },
     utils::{addr, pyerr},
 };
 
@@ -40,19 +47,41 @@ pub struct EVM {
     #[pyo3(get, set)]
     tracing: bool,
 
+    /// whether to record a structured call-frame tree for the last transaction, retrievable via
+    /// `call_trace`
+    #[pyo3(get, set)]
+    call_tracing: bool,
+
+    /// whether to record a structured opcode-level trace for the last transaction, retrievable
+    /// via `trace`
+    #[pyo3(get, set)]
+    op_tracing: bool,
+
     /// Checkpoints for reverting state
     /// We cannot use Revm's checkpointing mechanism as it is not serializable
     checkpoints: HashMap<JournalCheckpoint, RevmCheckpoint>,
 
     /// The result of the last transaction
     result: Option<RevmExecutionResult>,
+
+    /// The call-frame tree captured by `CallTracer` for the last transaction, if `call_tracing`
+    /// was enabled.
+    call_trace: Option<CallFrame>,
+
+    /// The opcode-level steps captured by `OpcodeTracer` for the last transaction, if
+    /// `op_tracing` was enabled.
+    trace: Vec<TraceStep>,
+
+    /// Precompiles defined from Python, installed at a chosen address.
+    custom_precompiles: CustomPrecompiles,
 }
 
 #[pymethods]
 impl EVM {
     /// Create a new EVM instance.
     #[new]
-    #[pyo3(signature = (env = None, fork_url = None, fork_block = None, gas_limit = 18446744073709551615, tracing = false, spec_id = "LATEST"))]
+    #[pyo3(signature = (env = None, fork_url = None, fork_block = None, gas_limit = 18446744073709551615, tracing = false, spec_id = "LATEST", op_stack = false, call_tracing = false, op_tracing = false, db = None))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         env: Option<Env>,
         fork_url: Option<&str>,
@@ -60,24 +89,45 @@ impl EVM {
         gas_limit: u64,
         tracing: bool,
         spec_id: &str,
+        op_stack: bool,
+        call_tracing: bool,
+        op_tracing: bool,
+        db: Option<PyObject>,
     ) -> PyResult<Self> {
         let spec = SpecId::from(spec_id);
         let env = env.unwrap_or_default().into();
-        let db = fork_url
-            .map(|url| DB::new_fork(url, fork_block))
-            .unwrap_or(Ok(DB::new_memory()))?;
+        let db = match (fork_url, db) {
+            (Some(url), _) => DB::new_fork(url, fork_block)?,
+            (None, Some(provider)) => DB::new_python(provider),
+            (None, None) => DB::new_memory(),
+        };
 
         let Evm { context, .. } = Evm::builder().with_env(Box::new(env)).with_db(db).build();
+        let mut handler_cfg = HandlerCfg::new(spec);
+        handler_cfg.is_optimism = op_stack;
         Ok(EVM {
             context: context.evm,
             gas_limit: U256::from(gas_limit),
-            handler_cfg: HandlerCfg::new(spec),
+            handler_cfg,
             tracing,
+            call_tracing,
+            op_tracing,
             checkpoints: HashMap::new(),
             result: None,
+            call_trace: None,
+            trace: Vec::new(),
+            custom_precompiles: HashMap::new(),
         })
     }
 
+    /// Registers a custom precompile at `address`. `precompile` is a Python callable receiving
+    /// `(input: bytes, gas_limit: int)` and returning `(output: bytes, gas_used: int)`; raising
+    /// from it signals an out-of-gas/error condition.
+    fn insert_precompile(&mut self, address: &str, precompile: PyObject) -> PyResult<()> {
+        self.custom_precompiles.insert(addr(address)?, precompile);
+        Ok(())
+    }
+
     fn snapshot(&mut self) -> PyResult<JournalCheckpoint> {
         let checkpoint = JournalCheckpoint {
             log_i: self.context.journaled_state.logs.len(),
@@ -110,6 +160,85 @@ impl EVM {
         self.context.journaled_state.checkpoint_commit();
     }
 
+    /// Walks the journal entries recorded since `from_checkpoint` and returns, for every address
+    /// they touched, its balance/nonce/code/storage before and after - an equivalent of
+    /// openethereum's `analytics.state_diffing` that replays the journal instead of cloning the
+    /// whole state up front.
+    fn state_diff(&self, from_checkpoint: JournalCheckpoint) -> HashMap<String, AccountDiff> {
+        let mut diffs: HashMap<Address, AccountDiff> = HashMap::new();
+        // Total balance received and total balance sent since the checkpoint, used to back out
+        // the pre-checkpoint balance from the current one.
+        let mut balance_delta: HashMap<Address, (U256, U256)> = HashMap::new();
+        let mut nonce_bumps: HashMap<Address, u64> = HashMap::new();
+
+        for entry in self
+            .context
+            .journaled_state
+            .journal
+            .iter()
+            .skip(from_checkpoint.journal_i)
+            .flatten()
+        {
+            match entry {
+                JournalEntry::BalanceTransfer { from, to, balance } => {
+                    let sent = balance_delta.entry(*from).or_default();
+                    sent.1 += *balance;
+                    let received = balance_delta.entry(*to).or_default();
+                    received.0 += *balance;
+                    diffs.entry(*from).or_default();
+                    diffs.entry(*to).or_default();
+                }
+                JournalEntry::NonceChange { address } => {
+                    // A nonce is only ever bumped by one per `NonceChange` entry.
+                    *nonce_bumps.entry(*address).or_default() += 1;
+                    diffs.entry(*address).or_default();
+                }
+                JournalEntry::CodeChange { address } => {
+                    diffs.entry(*address).or_default().code_changed = true;
+                }
+                JournalEntry::StorageChange {
+                    address,
+                    key,
+                    had_value,
+                } => {
+                    let diff = diffs.entry(*address).or_default();
+                    diff.storage
+                        .entry(*key)
+                        .or_insert((had_value.unwrap_or_default(), U256::ZERO));
+                }
+                _ => {}
+            }
+        }
+
+        for (address, diff) in diffs.iter_mut() {
+            let info = self
+                .context
+                .journaled_state
+                .state
+                .get(address)
+                .map(|acc| acc.info.clone())
+                .unwrap_or_default();
+            diff.balance_after = info.balance;
+            diff.nonce_after = info.nonce;
+            let (received, sent) = balance_delta.get(address).copied().unwrap_or_default();
+            diff.balance_before = diff.balance_after + sent - received;
+            diff.nonce_before = diff
+                .nonce_after
+                .saturating_sub(nonce_bumps.get(address).copied().unwrap_or_default());
+            let account = self.context.journaled_state.state.get(address);
+            for (slot, (before, after)) in diff.storage.iter_mut() {
+                *after = account
+                    .and_then(|acc| acc.storage.get(slot).copied())
+                    .unwrap_or(*before);
+            }
+        }
+
+        diffs
+            .into_iter()
+            .map(|(address, diff)| (address.to_string(), diff))
+            .collect()
+    }
+
     /// Get basic account information.
     fn basic(&mut self, address: &str) -> PyResult<AccountInfo> {
         let (account, _) = self.context.load_account(addr(address)?).map_err(pyerr)?;
@@ -166,7 +295,25 @@ impl EVM {
         Ok(balance)
     }
 
-    #[pyo3(signature = (caller, to, calldata = None, value = None, gas = None, gas_price = None, is_static = false))]
+    /// For a forked `EVM`, concurrently fetches `addresses`' account info and the given
+    /// `(address, slot)` storage pairs ahead of time and populates the in-memory cache, so a
+    /// subsequent `message_call`/`deploy` runs entirely against warm local state instead of one
+    /// blocking RPC round trip per touched account/slot. A no-op when not forked.
+    #[pyo3(signature = (addresses = Vec::new(), slots = Vec::new()))]
+    fn prefetch(&mut self, addresses: Vec<String>, slots: Vec<(String, U256)>) -> PyResult<()> {
+        let addresses = addresses
+            .iter()
+            .map(|address| addr(address))
+            .collect::<PyResult<Vec<_>>>()?;
+        let slots = slots
+            .into_iter()
+            .map(|(address, index)| Ok((addr(&address)?, index)))
+            .collect::<PyResult<Vec<_>>>()?;
+        self.context.db.prefetch(addresses, slots)
+    }
+
+    #[pyo3(signature = (caller, to, calldata = None, value = None, gas = None, gas_price = None, is_static = false, access_list = None))]
+    #[allow(clippy::too_many_arguments)]
     pub fn message_call(
         &mut self,
         caller: &str,
@@ -176,6 +323,7 @@ impl EVM {
         gas: Option<U256>,
         gas_price: Option<U256>,
         is_static: bool,
+        access_list: Option<Vec<(String, Vec<U256>)>>,
         py: Python<'_>,
     ) -> PyResult<PyObject> {
         let env = self.build_test_env(
@@ -185,6 +333,7 @@ impl EVM {
             value.unwrap_or_default(),
             gas,
             gas_price,
+            parse_access_list(access_list)?,
         );
         match self.call_with_env(env, is_static) {
             Ok(data) => Ok(PyBytes::new(py, data.as_ref()).into()),
@@ -192,8 +341,78 @@ impl EVM {
         }
     }
 
+    /// Runs a call against a temporary overlay of the database with the given per-account
+    /// `overrides` (balance/nonce/code/storage), then discards the overlay so the underlying
+    /// `DB::Memory`/`DB::Fork` is never mutated. This is the `eth_call` state-override pattern.
+    #[pyo3(signature = (caller, to, overrides, calldata = None, value = None, gas = None, gas_price = None, is_static = false))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn call_with_overrides(
+        &mut self,
+        caller: &str,
+        to: &str,
+        overrides: HashMap<String, AccountOverride>,
+        calldata: Option<PyByteVec>,
+        value: Option<U256>,
+        gas: Option<U256>,
+        gas_price: Option<U256>,
+        is_static: bool,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        let mut db_overrides = HashMap::new();
+        for (address, over) in overrides {
+            db_overrides.insert(addr(&address)?, over.into());
+        }
+
+        let env = self.build_test_env(
+            addr(caller)?,
+            TransactTo::Call(addr(to)?),
+            calldata.unwrap_or_default().into(),
+            value.unwrap_or_default(),
+            gas,
+            gas_price,
+            Vec::new(),
+        );
+
+        // `JournaledState` only consults the `Database`/`DatabaseRef` impl (and therefore the
+        // overlay) on a cold load, so an account already warmed by an earlier call in this `EVM`
+        // would otherwise keep using its real, non-overridden state. Evict the overridden
+        // addresses here to force a fresh overlay load, then restore whatever was cached before
+        // the call once it's done.
+        let warmed: HashMap<Address, Option<Account>> = db_overrides
+            .keys()
+            .map(|address| (*address, self.context.journaled_state.state.remove(address)))
+            .collect();
+
+        let checkpoint = self.context.journaled_state.checkpoint();
+        let original_db = replace(&mut self.context.db, DB::new_memory());
+        self.context.db = original_db.with_overrides(db_overrides);
+
+        let result = self.call_with_env(env, is_static);
+
+        let overlay_db = replace(&mut self.context.db, DB::new_memory());
+        self.context.db = overlay_db.into_inner();
+        self.context.journaled_state.checkpoint_revert(checkpoint);
+
+        for (address, account) in warmed {
+            match account {
+                Some(account) => {
+                    self.context.journaled_state.state.insert(address, account);
+                }
+                None => {
+                    self.context.journaled_state.state.remove(&address);
+                }
+            }
+        }
+
+        match result {
+            Ok(data) => Ok(PyBytes::new(py, data.as_ref()).into()),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Deploy a contract with the given code.
-    #[pyo3(signature = (deployer, code, value = None, gas = None, gas_price = None, is_static = false, _abi = None))]
+    #[pyo3(signature = (deployer, code, value = None, gas = None, gas_price = None, is_static = false, _abi = None, access_list = None))]
+    #[allow(clippy::too_many_arguments)]
     fn deploy(
         &mut self,
         deployer: &str,
@@ -203,6 +422,7 @@ impl EVM {
         gas_price: Option<U256>,
         is_static: bool,
         _abi: Option<&str>,
+        access_list: Option<Vec<(String, Vec<U256>)>>,
     ) -> PyResult<String> {
         let env = self.build_test_env(
             addr(deployer)?,
@@ -211,6 +431,7 @@ impl EVM {
             value.unwrap_or_default(),
             gas,
             gas_price,
+            parse_access_list(access_list)?,
         );
         match self.deploy_with_env(env, is_static) {
             Ok((_, address)) => Ok(format!("{:?}", address)),
@@ -228,6 +449,18 @@ impl EVM {
         self.result.clone().map(|r| r.into())
     }
 
+    /// The call-frame tree captured for the last transaction, if `call_tracing` was enabled.
+    #[getter]
+    fn call_trace(&self) -> Option<CallFrame> {
+        self.call_trace.clone()
+    }
+
+    /// The opcode-level steps captured for the last transaction, if `op_tracing` was enabled.
+    #[getter]
+    fn trace(&self) -> Vec<TraceStep> {
+        self.trace.clone()
+    }
+
     #[getter]
     fn checkpoint_ids(&self) -> HashSet<JournalCheckpoint> {
         self.checkpoints.keys().cloned().collect()
@@ -276,20 +509,210 @@ impl EVM {
         self.context.env.tx = tx.into();
     }
 
+    /// Skip the caller's nonce check, so a transaction can be simulated from an account with a
+    /// stale nonce without having to patch it first. This is the `eth_call`-style use case where
+    /// validation should be as permissive as possible.
+    fn set_disable_nonce_check(&mut self, disable: bool) {
+        self.context.env.cfg.disable_nonce_check = disable;
+    }
+
+    /// Skip the caller's balance check (and the corresponding deduction), so a transaction can be
+    /// simulated from an account that can't actually afford `gas_limit * gas_price + value`.
+    fn set_disable_balance_check(&mut self, disable: bool) {
+        self.context.env.cfg.disable_balance_check = disable;
+    }
+
+    /// Skip EIP-1559 base fee validation, so a transaction can be simulated regardless of the
+    /// current block's `basefee`.
+    fn set_disable_base_fee(&mut self, disable: bool) {
+        self.context.env.cfg.disable_base_fee = disable;
+    }
+
+    /// Skip the check that a transaction's `gas_limit` doesn't exceed the block's gas limit.
+    fn set_disable_block_gas_limit(&mut self, disable: bool) {
+        self.context.env.cfg.disable_block_gas_limit = disable;
+    }
+
     fn reset_transient_storage(&mut self) {
         self.context.journaled_state.transient_storage.clear();
     }
 
+    /// Dumps the accounts, code, and storage currently cached in the database to `path`, so that
+    /// expensive `DB::Fork` lookups against a remote provider can be cached between process runs.
+    fn dump_state(&self, path: &str) -> PyResult<()> {
+        self.context.db.dump_snapshot(path)
+    }
+
+    /// Replaces the current database with a fresh `DB::Memory` rehydrated from a snapshot
+    /// written by `dump_state`.
+    fn load_state(&mut self, path: &str) -> PyResult<()> {
+        self.context.db = DB::load_snapshot(path)?;
+        Ok(())
+    }
+
     fn __str__(&self) -> String {
         format!("{:?}", self)
     }
 }
 
+/// Turns a non-`Success` `ExecutionResult` into the `PyErr` raised to the caller: a `Revert`
+/// raises `RevertError` carrying the revert output and gas used so it can be caught and
+/// ABI-decoded, while a `Halt` falls back to the generic `pyerr` formatting.
+fn execution_err(result: RevmExecutionResult) -> pyo3::PyErr {
+    match result {
+        RevmExecutionResult::Revert { output, gas_used } => Python::with_gil(|py| {
+            let output: PyObject = PyBytes::new(py, &output).into();
+            RevertError::new_err((output, gas_used))
+        }),
+        other => pyerr(other),
+    }
+}
+
+/// Converts the `(address, [storage_keys])` tuples accepted by `message_call`/`deploy` into
+/// revm's EIP-2930 access list representation.
+fn parse_access_list(
+    access_list: Option<Vec<(String, Vec<U256>)>>,
+) -> PyResult<Vec<(Address, Vec<U256>)>> {
+    access_list
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(address, keys)| Ok((addr(&address)?, keys)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use revm::primitives::{AccountInfo as RevmAccountInfo, Bytecode};
+
+    use super::*;
+
+    fn new_evm() -> EVM {
+        EVM::new(
+            None,
+            None,
+            None,
+            u64::MAX,
+            false,
+            "LATEST",
+            false,
+            false,
+            false,
+            None,
+        )
+        .unwrap()
+    }
+
+    /// Regression test: an account that starts at 10, receives 100, then sends 105 within the
+    /// same checkpoint ends at 5 - reconstructing `balance_before` must not underflow the
+    /// intermediate `U256` subtraction.
+    #[test]
+    fn state_diff_reconstructs_balance_without_underflow() {
+        let mut evm = new_evm();
+        let address = Address::repeat_byte(0x01);
+        let other_in = Address::repeat_byte(0x02);
+        let other_out = Address::repeat_byte(0x03);
+
+        evm.context
+            .journaled_state
+            .state
+            .insert(address, RevmAccountInfo::default().into());
+        evm.context
+            .journaled_state
+            .state
+            .get_mut(&address)
+            .unwrap()
+            .info
+            .balance = U256::from(5);
+
+        let checkpoint = evm.snapshot().unwrap();
+        evm.context.journaled_state.journal.push(vec![
+            JournalEntry::BalanceTransfer {
+                from: other_in,
+                to: address,
+                balance: U256::from(100),
+            },
+            JournalEntry::BalanceTransfer {
+                from: address,
+                to: other_out,
+                balance: U256::from(105),
+            },
+        ]);
+
+        let diffs = evm.state_diff(checkpoint);
+        let diff = &diffs[&address.to_string()];
+        assert_eq!(diff.balance_after, U256::from(5));
+        assert_eq!(diff.balance_before, U256::from(10));
+    }
+
+    /// Regression test: a prior call warms `address` with its real balance, so a later
+    /// `call_with_overrides` must still honor the override instead of silently using the
+    /// already-cached `journaled_state` entry.
+    #[test]
+    fn call_with_overrides_applies_to_an_already_warmed_account() {
+        let mut evm = new_evm();
+        let address = Address::repeat_byte(0x01);
+        let caller = Address::repeat_byte(0x02);
+
+        // Returns `SELFBALANCE` from the first 32 bytes of memory.
+        let code =
+            Bytecode::new_raw(vec![0x47, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3].into());
+        evm.context.db.insert_account_info(
+            address,
+            RevmAccountInfo {
+                balance: U256::from(10),
+                nonce: 0,
+                code_hash: code.hash_slow(),
+                code: Some(code),
+            },
+        );
+
+        // Warm the account with its real balance before overriding it.
+        evm.context.load_account(address).unwrap();
+        assert_eq!(
+            evm.context.journaled_state.state[&address].info.balance,
+            U256::from(10)
+        );
+
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            address.to_string(),
+            AccountOverride {
+                balance: Some(U256::from(999)),
+                nonce: None,
+                code: None,
+                storage: HashMap::new(),
+                storage_reset: false,
+            },
+        );
+
+        let output = Python::with_gil(|py| {
+            evm.call_with_overrides(
+                &caller.to_string(),
+                &address.to_string(),
+                overrides,
+                None,
+                None,
+                None,
+                None,
+                false,
+                py,
+            )
+            .unwrap()
+        });
+
+        Python::with_gil(|py| {
+            let bytes: &PyBytes = output.extract(py).unwrap();
+            assert_eq!(U256::try_from_be_slice(bytes.as_bytes()).unwrap(), U256::from(999));
+        });
+    }
+}
+
 impl EVM {
     /// Creates the environment to use when executing a transaction in a test context
     ///
     /// If using a backend with cheat codes, `tx.gas_price` and `block.number` will be overwritten by
     /// the cheatcode state inbetween calls.
+    #[allow(clippy::too_many_arguments)]
     fn build_test_env(
         &self,
         caller: Address,
@@ -298,6 +721,7 @@ impl EVM {
         value: U256,
         gas: Option<U256>,
         gas_price: Option<U256>,
+        access_list: Vec<(Address, Vec<U256>)>,
     ) -> RevmEnv {
         RevmEnv {
             cfg: self.context.env.cfg.clone(),
@@ -318,6 +742,7 @@ impl EVM {
                 gas_price: gas_price.unwrap_or(U256::ZERO),
                 gas_priority_fee: None,
                 gas_limit: gas.unwrap_or(self.gas_limit).to(),
+                access_list,
                 ..self.context.env.tx.clone()
             },
         }
@@ -341,7 +766,7 @@ impl EVM {
                 Err(pyerr(output))
             }
         } else {
-            Err(pyerr(result))
+            Err(execution_err(result))
         }
     }
 
@@ -360,7 +785,7 @@ impl EVM {
                 Err(pyerr(output))
             }
         } else {
-            Err(pyerr(result))
+            Err(execution_err(result))
         }
     }
 
@@ -368,10 +793,19 @@ impl EVM {
         self.context.env = Box::new(env);
         let evm_context: EvmContext<DB> =
             replace(&mut self.context, EvmContext::new(DB::new_memory()));
-        let (result, evm_context) =
-            call_evm(evm_context, self.handler_cfg, self.tracing, is_static);
+        let (result, evm_context, call_trace, trace) = call_evm(
+            evm_context,
+            self.handler_cfg,
+            self.tracing,
+            self.call_tracing,
+            self.op_tracing,
+            self.custom_precompiles.clone(),
+            is_static,
+        );
         self.context = evm_context;
         self.result = result.as_ref().ok().cloned();
+        self.call_trace = call_trace;
+        self.trace = trace;
         result
     }
 }
@@ -0,0 +1,11 @@
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+
+create_exception!(
+    pyrevm,
+    RevertError,
+    PyException,
+    "Raised by `EVM.message_call`/`EVM.deploy` when the transaction reverts. Args are \
+     `(output: bytes, gas_used: int)` so callers can ABI-decode `Error(string)` or a custom \
+     error instead of losing the revert payload to a stringified debug blob."
+);
@@ -1,31 +1,76 @@
+use std::collections::HashMap;
 use std::mem::replace;
+use std::sync::Arc;
 
 use pyo3::exceptions::PyRuntimeError;
-use pyo3::PyResult;
+use pyo3::{PyObject, PyResult};
 use revm::inspectors::TracerEip3155;
-use revm::precompile::Log;
+use revm::precompile::{Address, Log};
 use revm::primitives::TransactTo;
-use revm::primitives::{ExecutionResult, ShanghaiSpec};
+use revm::primitives::{EVMError, ExecutionResult, ShanghaiSpec};
+#[cfg(feature = "optimism")]
+use revm::optimism::optimism_handle_register;
 use revm::{
     inspector_handle_register, Context, ContextWithHandlerCfg, Evm, EvmContext, FrameOrResult,
-    FrameResult,
+    FrameResult, Handler,
 };
 use revm_interpreter::primitives::HandlerCfg;
 use revm_interpreter::{gas, CallInputs, CreateInputs, SuccessOrHalt};
 
 use crate::database::DB;
+use crate::inspector::{CallTracer, OpcodeTracer};
+use crate::precompile::python_precompile;
+use crate::types::{CallFrame, TraceStep};
 use crate::utils::pyerr;
 
+/// Custom precompiles registered from Python, keyed by their installed address.
+pub(crate) type CustomPrecompiles = HashMap<Address, PyObject>;
+
+/// A handler register that layers the caller's custom, Python-backed precompiles on top of
+/// whatever the handler would otherwise load.
+fn custom_precompile_register<EXT: 'static>(
+    custom_precompiles: CustomPrecompiles,
+) -> impl Fn(&mut Handler<'_, EXT, DB>) {
+    move |handler| {
+        let custom_precompiles = custom_precompiles.clone();
+        let prev = handler.pre_execution.load_precompiles.clone();
+        handler.pre_execution.load_precompiles = Arc::new(move || {
+            let mut loaded = prev();
+            loaded.extend(
+                custom_precompiles
+                    .iter()
+                    .map(|(address, callable)| (*address, python_precompile(callable.clone()))),
+            );
+            loaded
+        });
+    }
+}
+
 /// Calls the EVM with the given context and handler configuration.
+///
+/// When `tracing` is set, opcode-level output is streamed to Python's stdout via the
+/// `TracerEip3155` inspector. Otherwise, when `call_tracing` is set, a `CallTracer` inspector
+/// builds a structured call-frame tree that is returned alongside the `ExecutionResult`.
+/// Otherwise, when `op_tracing` is set, an `OpcodeTracer` inspector records a structured
+/// [`TraceStep`] per executed opcode.
+#[allow(clippy::type_complexity)]
 pub(crate) fn call_evm(
     evm_context: EvmContext<DB>,
     handler_cfg: HandlerCfg,
     tracing: bool,
+    call_tracing: bool,
+    op_tracing: bool,
+    custom_precompiles: CustomPrecompiles,
     is_static: bool,
-) -> (PyResult<ExecutionResult>, EvmContext<DB>) {
+) -> (
+    PyResult<ExecutionResult>,
+    EvmContext<DB>,
+    Option<CallFrame>,
+    Vec<TraceStep>,
+) {
     if tracing {
         let tracer = TracerEip3155::new(Box::new(crate::pystdout::PySysStdout {}));
-        let mut evm = Evm::builder()
+        let mut builder = Evm::builder()
             .with_context_with_handler_cfg(ContextWithHandlerCfg {
                 cfg: handler_cfg,
                 context: Context {
@@ -33,27 +78,94 @@ pub(crate) fn call_evm(
                     external: tracer,
                 },
             })
-            .append_handler_register(inspector_handle_register)
-            .build();
-        (run_evm(&mut evm, is_static), evm.context.evm)
-    } else {
-        let mut evm = Evm::builder()
+            .append_handler_register(inspector_handle_register);
+        #[cfg(feature = "optimism")]
+        if handler_cfg.is_optimism {
+            builder = builder.append_handler_register(optimism_handle_register);
+        }
+        if !custom_precompiles.is_empty() {
+            builder = builder.append_handler_register(custom_precompile_register(custom_precompiles));
+        }
+        let mut evm = builder.build();
+        (run_evm(&mut evm, is_static), evm.context.evm, None, Vec::new())
+    } else if call_tracing {
+        let mut builder = Evm::builder()
+            .with_context_with_handler_cfg(ContextWithHandlerCfg {
+                cfg: handler_cfg,
+                context: Context {
+                    evm: evm_context,
+                    external: CallTracer::default(),
+                },
+            })
+            .append_handler_register(inspector_handle_register);
+        #[cfg(feature = "optimism")]
+        if handler_cfg.is_optimism {
+            builder = builder.append_handler_register(optimism_handle_register);
+        }
+        if !custom_precompiles.is_empty() {
+            builder = builder.append_handler_register(custom_precompile_register(custom_precompiles));
+        }
+        let mut evm = builder.build();
+        let result = run_evm(&mut evm, is_static);
+        let call_trace = evm.context.external.root.take();
+        (result, evm.context.evm, call_trace, Vec::new())
+    } else if op_tracing {
+        let mut builder = Evm::builder()
             .with_context_with_handler_cfg(ContextWithHandlerCfg {
                 cfg: handler_cfg,
                 context: Context {
                     evm: evm_context,
-                    external: (),
+                    external: OpcodeTracer::default(),
                 },
             })
-            .build();
-        (run_evm(&mut evm, is_static), evm.context.evm)
+            .append_handler_register(inspector_handle_register);
+        #[cfg(feature = "optimism")]
+        if handler_cfg.is_optimism {
+            builder = builder.append_handler_register(optimism_handle_register);
+        }
+        if !custom_precompiles.is_empty() {
+            builder = builder.append_handler_register(custom_precompile_register(custom_precompiles));
+        }
+        let mut evm = builder.build();
+        let result = run_evm(&mut evm, is_static);
+        let trace = std::mem::take(&mut evm.context.external.steps);
+        (result, evm.context.evm, None, trace)
+    } else {
+        let mut builder = Evm::builder().with_context_with_handler_cfg(ContextWithHandlerCfg {
+            cfg: handler_cfg,
+            context: Context {
+                evm: evm_context,
+                external: (),
+            },
+        });
+        #[cfg(feature = "optimism")]
+        if handler_cfg.is_optimism {
+            builder = builder.append_handler_register(optimism_handle_register);
+        }
+        if !custom_precompiles.is_empty() {
+            builder = builder.append_handler_register(custom_precompile_register(custom_precompiles));
+        }
+        let mut evm = builder.build();
+        (run_evm(&mut evm, is_static), evm.context.evm, None, Vec::new())
     }
 }
 
+/// Returns `true` if the given transaction is an Optimism deposit transaction, i.e. it carries
+/// an L1 `source_hash`.
+#[cfg(feature = "optimism")]
+fn is_deposit_tx(tx: &revm::primitives::TxEnv) -> bool {
+    tx.optimism.source_hash.is_some()
+}
+
 /// Calls the given evm. This is originally a copy of revm::Evm::transact, but it calls our own output function
 fn run_evm<EXT>(evm: &mut Evm<'_, EXT, DB>, is_static: bool) -> PyResult<ExecutionResult> {
     let logs_i = evm.context.evm.journaled_state.logs.len();
 
+    #[cfg(feature = "optimism")]
+    let is_deposit = evm.handler.cfg().is_optimism && is_deposit_tx(&evm.context.evm.env.tx);
+    #[cfg(not(feature = "optimism"))]
+    let is_deposit = false;
+
     evm.handler
         .validation()
         .env(&evm.context.evm.env)
@@ -76,10 +188,30 @@ fn run_evm<EXT>(evm: &mut Evm<'_, EXT, DB>, is_static: bool) -> PyResult<Executi
             ))
         })?;
 
-    evm.handler
-        .validation()
-        .tx_against_state(&mut evm.context)
-        .map_err(pyerr)?;
+    // Deposit transactions never fail on balance/nonce checks: the `mint` amount is credited to
+    // the caller before execution and the source chain has already committed to including the
+    // deposit. A system transaction that still fails validation post-Regolith is the one case
+    // that must surface as a hard `InvalidTransaction` error rather than being swallowed.
+    if let Err(e) = evm.handler.validation().tx_against_state(&mut evm.context) {
+        if !is_deposit {
+            return Err(pyerr(e));
+        }
+        #[cfg(feature = "optimism")]
+        {
+            let is_regolith = evm.handler.cfg().spec_id.is_enabled_in(SpecId::REGOLITH);
+            let is_system_tx = evm
+                .context
+                .evm
+                .env
+                .tx
+                .optimism
+                .is_system_transaction
+                .unwrap_or(false);
+            if is_regolith && is_system_tx {
+                return Err(pyerr(e));
+            }
+        }
+    }
 
     let ctx = &mut evm.context;
     let pre_exec = evm.handler.pre_execution();
@@ -158,10 +290,27 @@ fn output<EXT>(
     result: FrameResult,
     logs: Vec<Log>,
 ) -> PyResult<ExecutionResult> {
-    replace(&mut context.evm.error, Ok(())).map_err(pyerr)?;
+    if let Err(err) = replace(&mut context.evm.error, Ok(())) {
+        // A `DB` callback's own `PyErr` is the most useful error to surface as-is; anything else
+        // falls back to the generic debug-formatted conversion.
+        return Err(match err {
+            EVMError::Database(err) => err,
+            other => pyerr(other),
+        });
+    }
     // used gas with refund calculated.
     let gas_refunded = result.gas().refunded() as u64;
     let final_gas_used = result.gas().spent() - gas_refunded;
+
+    #[cfg(feature = "optimism")]
+    let is_deposit = is_deposit_tx(&context.evm.env.tx);
+    #[cfg(not(feature = "optimism"))]
+    let is_deposit = false;
+    // Deposits are never "dropped": a failed deposit still burns its gas instead of reverting
+    // state as a normal transaction would.
+    #[cfg(feature = "optimism")]
+    let gas_limit = context.evm.env.tx.gas_limit;
+
     let output = result.output();
     let instruction_result = result.into_interpreter_result();
 
@@ -173,6 +322,11 @@ fn output<EXT>(
             logs,
             output,
         },
+        #[cfg(feature = "optimism")]
+        SuccessOrHalt::Revert if is_deposit => ExecutionResult::Halt {
+            reason: revm::primitives::HaltReason::FailedDeposit,
+            gas_used: gas_limit,
+        },
         SuccessOrHalt::Revert => ExecutionResult::Revert {
             gas_used: final_gas_used,
             output: output.into_data(),
@@ -181,10 +335,15 @@ fn output<EXT>(
             reason,
             gas_used: final_gas_used,
         },
-        // Only two internal return flags.
-        SuccessOrHalt::FatalExternalError
-        | SuccessOrHalt::InternalContinue
-        | SuccessOrHalt::InternalCallOrCreate => {
+        // A database callback (e.g. a `DB::Python` provider) failed; surface it as a normal
+        // Python exception instead of aborting the interpreter.
+        SuccessOrHalt::FatalExternalError => {
+            return Err(PyRuntimeError::new_err(
+                "Fatal external error: a database callback failed during execution",
+            ))
+        }
+        // Only two remaining internal return flags; these should never escape the interpreter.
+        SuccessOrHalt::InternalContinue | SuccessOrHalt::InternalCallOrCreate => {
             panic!("Internal return flags should remain internal {instruction_result:?}")
         }
     };
@@ -0,0 +1,163 @@
+use revm::interpreter::{
+    opcode::OpCode, CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, Interpreter,
+};
+use revm::primitives::Log as RevmLog;
+use revm::{Database, EvmContext, Inspector};
+
+use crate::types::{CallFrame, TraceStep};
+
+/// Builds a [`CallFrame`] tree of every `CALL`/`STATICCALL`/`DELEGATECALL`/`CREATE` executed
+/// during a transaction, mirroring a `debug_traceTransaction` call tree. Registered via
+/// `append_handler_register` in the `call_tracing` branch of `call_evm`.
+#[derive(Debug, Default)]
+pub(crate) struct CallTracer {
+    stack: Vec<CallFrame>,
+    pub(crate) root: Option<CallFrame>,
+}
+
+impl CallTracer {
+    fn finish_frame(&mut self, gas_used: u64, output: Vec<u8>, reverted: bool) {
+        let Some(mut frame) = self.stack.pop() else {
+            return;
+        };
+        frame.gas_used = gas_used;
+        frame.revert_reason = reverted.then(|| decode_revert_reason(&output));
+        frame.output = output;
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.root = Some(frame),
+        }
+    }
+}
+
+/// Best-effort decoding of the standard `Error(string)` revert selector; falls back to a hex dump.
+fn decode_revert_reason(output: &[u8]) -> String {
+    if output.len() >= 68 && output[..4] == [0x08, 0xc3, 0x79, 0xa0] {
+        let len = u64::from_be_bytes(output[60..68].try_into().unwrap()) as usize;
+        if let Some(bytes) = output.get(68..68 + len) {
+            if let Ok(s) = std::str::from_utf8(bytes) {
+                return s.to_string();
+            }
+        }
+    }
+    revm::primitives::Bytes::copy_from_slice(output).to_string()
+}
+
+impl<DB: Database> Inspector<DB> for CallTracer {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let kind = match inputs.scheme {
+            CallScheme::Call => "CALL",
+            CallScheme::CallCode => "CALLCODE",
+            CallScheme::DelegateCall => "DELEGATECALL",
+            CallScheme::StaticCall => "STATICCALL",
+        };
+        self.stack.push(CallFrame {
+            kind: kind.to_string(),
+            from: inputs.caller.to_string(),
+            to: inputs.target_address.to_string(),
+            input: inputs.input.to_vec(),
+            value: inputs.value.get(),
+            gas: inputs.gas_limit,
+            ..Default::default()
+        });
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.finish_frame(
+            outcome.gas().spent(),
+            outcome.result.output.to_vec(),
+            !outcome.result.is_ok(),
+        );
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.stack.push(CallFrame {
+            kind: "CREATE".to_string(),
+            from: inputs.caller.to_string(),
+            input: inputs.init_code.to_vec(),
+            value: inputs.value,
+            gas: inputs.gas_limit,
+            ..Default::default()
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if let (Some(frame), Some(address)) = (self.stack.last_mut(), outcome.address) {
+            frame.to = address.to_string();
+        }
+        self.finish_frame(
+            outcome.gas().spent(),
+            outcome.result.output.to_vec(),
+            !outcome.result.is_ok(),
+        );
+        outcome
+    }
+
+    fn log(&mut self, _context: &mut EvmContext<DB>, log: &RevmLog) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.logs.push(log.clone().into());
+        }
+    }
+}
+
+/// Records a [`TraceStep`] for every opcode executed during a transaction, giving a structured
+/// equivalent of what `TracerEip3155` streams to stdout. Registered via
+/// `append_handler_register` in the `op_tracing` branch of `call_evm`.
+#[derive(Debug, Default)]
+pub(crate) struct OpcodeTracer {
+    pub(crate) steps: Vec<TraceStep>,
+    gas_remaining_before: u64,
+}
+
+impl<DB: Database> Inspector<DB> for OpcodeTracer {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.gas_remaining_before = interp.gas.remaining();
+        let opcode = interp.current_opcode();
+        let opcode_name = OpCode::new(opcode)
+            .map(|op| op.as_str().to_string())
+            .unwrap_or_else(|| format!("UNKNOWN(0x{opcode:02x})"));
+        self.steps.push(TraceStep {
+            pc: interp.program_counter() as u64,
+            opcode,
+            opcode_name,
+            gas: self.gas_remaining_before,
+            gas_cost: 0,
+            depth: context.journaled_state.depth as u64,
+            stack: interp
+                .stack
+                .data()
+                .iter()
+                .map(|word| format!("{word:#x}"))
+                .collect(),
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if let Some(step) = self.steps.last_mut() {
+            step.gas_cost = self
+                .gas_remaining_before
+                .saturating_sub(interp.gas.remaining());
+        }
+    }
+}
@@ -11,18 +11,26 @@ use pyo3::prelude::*;
 mod database;
 mod empty_db_wrapper;
 mod evm;
+mod exceptions;
 mod executor;
+mod inspector;
+mod precompile;
 mod pystdout;
+mod python_db;
+mod snapshot;
+mod tx_decode;
 mod types;
 mod utils;
 
 pub use evm::EVM;
+pub use exceptions::RevertError;
 pub use types::*;
 pub use utils::fake_exponential;
 
 #[pymodule]
-fn pyrevm(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+fn pyrevm(py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<EVM>()?;
+    m.add("RevertError", py.get_type::<RevertError>())?;
 
     // Types
     m.add_class::<AccountInfo>()?;
@@ -33,6 +41,9 @@ fn pyrevm(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<ExecutionResult>()?;
     m.add_class::<Log>()?;
     m.add_class::<JournalCheckpoint>()?;
+    m.add_class::<CallFrame>()?;
+    m.add_class::<TraceStep>()?;
+    m.add_class::<AccountDiff>()?;
     m.add_function(wrap_pyfunction!(fake_exponential, m)?)?;
 
     Ok(())
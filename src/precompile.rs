@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use pyo3::types::PyBytes;
+use pyo3::{PyObject, Python};
+use revm::precompile::{Precompile, PrecompileError, PrecompileResult, StatefulPrecompile};
+use revm::primitives::{Bytes, Env};
+
+/// Wraps a Python callable as a revm stateful precompile.
+///
+/// The callable receives the input bytes and the gas limit, and must return
+/// `(output_bytes, gas_used)`. Raising from Python (e.g. to signal out-of-gas) is surfaced as a
+/// [`PrecompileError::other`].
+pub(crate) struct PyPrecompile {
+    callable: PyObject,
+}
+
+impl PyPrecompile {
+    pub(crate) fn new(callable: PyObject) -> Self {
+        Self { callable }
+    }
+}
+
+impl StatefulPrecompile for PyPrecompile {
+    fn call(&self, bytes: &Bytes, gas_limit: u64, _env: &Env) -> PrecompileResult {
+        Python::with_gil(|py| {
+            let input = PyBytes::new(py, bytes.as_ref());
+            let result = self
+                .callable
+                .call1(py, (input, gas_limit))
+                .map_err(|e| PrecompileError::other(e.to_string()))?;
+            let (output, gas_used): (Vec<u8>, u64) = result
+                .extract(py)
+                .map_err(|e| PrecompileError::other(e.to_string()))?;
+            Ok((gas_used, Bytes::from(output)))
+        })
+    }
+}
+
+/// Builds the `Precompile` variant for a Python-defined precompile.
+pub(crate) fn python_precompile(callable: PyObject) -> Precompile {
+    Precompile::Stateful(Arc::new(PyPrecompile::new(callable)))
+}
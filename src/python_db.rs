@@ -0,0 +1,73 @@
+use pyo3::exceptions::PyTypeError;
+use pyo3::types::PyBytes;
+use pyo3::{PyErr, PyObject, PyResult, Python};
+use revm::precompile::{Address, B256};
+use revm::primitives::{AccountInfo, Bytecode, U256};
+
+use crate::types::AccountInfo as PyAccountInfo;
+
+/// A [`DB::Python`](crate::database::DB::Python) backend delegating every lookup to a Python
+/// object implementing `basic`, `code_by_hash`, `storage`, and `block_hash`.
+///
+/// Errors raised from Python propagate as `PyErr` rather than panicking, so a misbehaving
+/// provider surfaces as a catchable exception instead of aborting the interpreter.
+pub(crate) struct PythonDB(PyObject);
+
+impl PythonDB {
+    pub(crate) fn new(provider: PyObject) -> Self {
+        Self(provider)
+    }
+
+    pub(crate) fn basic(&self, address: Address) -> PyResult<Option<AccountInfo>> {
+        Python::with_gil(|py| {
+            let result = self.0.call_method1(py, "basic", (address.to_string(),))?;
+            if result.is_none(py) {
+                return Ok(None);
+            }
+            let info: PyAccountInfo = result.extract(py)?;
+            Ok(Some(info.into()))
+        })
+    }
+
+    pub(crate) fn code_by_hash(&self, code_hash: B256) -> PyResult<Bytecode> {
+        Python::with_gil(|py| {
+            let result = self.0.call_method1(
+                py,
+                "code_by_hash",
+                (PyBytes::new(py, code_hash.as_ref()),),
+            )?;
+            let bytes: Vec<u8> = result.extract(py)?;
+            Ok(Bytecode::new_raw(bytes.into()))
+        })
+    }
+
+    pub(crate) fn storage(&self, address: Address, index: U256) -> PyResult<U256> {
+        Python::with_gil(|py| {
+            let result = self
+                .0
+                .call_method1(py, "storage", (address.to_string(), index))?;
+            result.extract(py)
+        })
+    }
+
+    pub(crate) fn block_hash(&self, number: U256) -> PyResult<B256> {
+        Python::with_gil(|py| {
+            let result = self.0.call_method1(py, "block_hash", (number,))?;
+            let bytes: Vec<u8> = result.extract(py)?;
+            B256::try_from(bytes.as_slice())
+                .map_err(|e| PyTypeError::new_err(e.to_string()) as PyErr)
+        })
+    }
+}
+
+impl Clone for PythonDB {
+    fn clone(&self) -> Self {
+        Python::with_gil(|py| PythonDB(self.0.clone_ref(py)))
+    }
+}
+
+impl std::fmt::Debug for PythonDB {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PythonDB(..)")
+    }
+}
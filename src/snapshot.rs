@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::fs;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::PyResult;
+use revm::primitives::{Address, Bytecode, B256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::database::DB;
+use crate::utils::pyerr;
+
+#[derive(Serialize, Deserialize)]
+struct DbSnapshot {
+    accounts: Vec<SnapshotAccount>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotAccount {
+    address: Address,
+    balance: U256,
+    nonce: u64,
+    code_hash: B256,
+    code: Option<Vec<u8>>,
+    storage: HashMap<U256, U256>,
+}
+
+impl DB {
+    /// Serializes the current accounts/code/storage to `path`.
+    pub(crate) fn dump_snapshot(&self, path: &str) -> PyResult<()> {
+        let accounts = self
+            .get_accounts()
+            .iter()
+            .map(|(address, account)| SnapshotAccount {
+                address: *address,
+                balance: account.info.balance,
+                nonce: account.info.nonce,
+                code_hash: account.info.code_hash,
+                code: account
+                    .info
+                    .code
+                    .as_ref()
+                    .map(|code| code.bytecode.to_vec()),
+                storage: account.storage.clone(),
+            })
+            .collect();
+        let json = serde_json::to_string(&DbSnapshot { accounts }).map_err(pyerr)?;
+        fs::write(path, json).map_err(pyerr)
+    }
+
+    /// Loads a snapshot written by `dump_snapshot` back into a fresh `DB::Memory`.
+    pub(crate) fn load_snapshot(path: &str) -> PyResult<Self> {
+        let json = fs::read_to_string(path).map_err(pyerr)?;
+        let snapshot: DbSnapshot = serde_json::from_str(&json)
+            .map_err(|e| PyValueError::new_err(format!("Corrupt or incomplete snapshot: {e}")))?;
+
+        let mut db = DB::new_memory();
+        for account in snapshot.accounts {
+            let code = match account.code {
+                Some(bytes) => {
+                    let code = Bytecode::new_raw(bytes.into());
+                    if code.hash_slow() != account.code_hash {
+                        return Err(PyValueError::new_err(format!(
+                            "Corrupt snapshot: code hash mismatch for {}",
+                            account.address
+                        )));
+                    }
+                    Some(code)
+                }
+                None => None,
+            };
+            db.insert_account_info(
+                account.address,
+                revm::primitives::AccountInfo {
+                    balance: account.balance,
+                    nonce: account.nonce,
+                    code_hash: account.code_hash,
+                    code,
+                },
+            );
+            for (slot, value) in account.storage {
+                db.insert_account_storage(account.address, slot, value)?;
+            }
+        }
+        Ok(db)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use revm::primitives::AccountInfo;
+
+    use super::*;
+
+    #[test]
+    fn dump_and_load_snapshot_roundtrips() {
+        let path = std::env::temp_dir().join("pyrevm_snapshot_roundtrip_test.json");
+
+        let address = Address::repeat_byte(0x42);
+        let code = Bytecode::new_raw(vec![0x60, 0x00].into());
+        let mut db = DB::new_memory();
+        db.insert_account_info(
+            address,
+            AccountInfo {
+                balance: U256::from(100),
+                nonce: 3,
+                code_hash: code.hash_slow(),
+                code: Some(code),
+            },
+        );
+        db.insert_account_storage(address, U256::from(1), U256::from(2))
+            .unwrap();
+
+        db.dump_snapshot(path.to_str().unwrap()).unwrap();
+        let loaded = DB::load_snapshot(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let account = loaded.get_accounts().get(&address).unwrap();
+        assert_eq!(account.info.balance, U256::from(100));
+        assert_eq!(account.info.nonce, 3);
+        assert_eq!(account.storage.get(&U256::from(1)), Some(&U256::from(2)));
+    }
+
+    #[test]
+    fn load_snapshot_rejects_corrupt_file() {
+        let path = std::env::temp_dir().join("pyrevm_snapshot_corrupt_test.json");
+        fs::write(&path, "not json").unwrap();
+        let result = DB::load_snapshot(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn load_snapshot_rejects_code_hash_mismatch() {
+        let path = std::env::temp_dir().join("pyrevm_snapshot_hash_mismatch_test.json");
+
+        let snapshot = DbSnapshot {
+            accounts: vec![SnapshotAccount {
+                address: Address::repeat_byte(0x42),
+                balance: U256::from(100),
+                nonce: 0,
+                code_hash: B256::repeat_byte(0xaa),
+                code: Some(vec![0x60, 0x00]),
+                storage: HashMap::new(),
+            }],
+        };
+        fs::write(&path, serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        let result = DB::load_snapshot(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,505 @@
+use ethers_core::types::{RecoveryMessage, Signature as EthersSignature, H160, U256 as EthersU256};
+use ethers_core::utils::keccak256;
+use ethers_core::utils::rlp::{DecoderError, Rlp};
+use pyo3::exceptions::PyValueError;
+use pyo3::PyResult;
+use revm::primitives::{Address, CreateScheme, TransactTo, TxEnv as RevmTxEnv, B256, U256};
+
+use crate::utils::pyerr;
+
+/// Decodes a signed, RLP-encoded Ethereum transaction - legacy, or an EIP-2718 typed envelope
+/// (EIP-2930, EIP-1559, EIP-4844) - into a `TxEnv`, recovering `caller` by `ecrecover`-ing the
+/// signature over the transaction's sighash.
+pub(crate) fn decode_raw_tx(raw: &[u8]) -> PyResult<RevmTxEnv> {
+    match raw.first() {
+        Some(0x01) => decode_eip2930(&raw[1..]),
+        Some(0x02) => decode_eip1559(&raw[1..]),
+        Some(0x03) => decode_eip4844(&raw[1..]),
+        Some(b) if *b >= 0xc0 => decode_legacy(raw),
+        _ => Err(PyValueError::new_err(
+            "not a recognized RLP-encoded transaction",
+        )),
+    }
+}
+
+fn eu256_to_u256(v: EthersU256) -> U256 {
+    let mut buf = [0u8; 32];
+    v.to_big_endian(&mut buf);
+    U256::from_be_bytes(buf)
+}
+
+fn to_address(rlp: &Rlp<'_>, index: usize) -> Result<Option<Address>, DecoderError> {
+    let data = rlp.at(index)?.data()?;
+    if data.is_empty() {
+        Ok(None)
+    } else if data.len() == 20 {
+        Ok(Some(Address::from_slice(data)))
+    } else {
+        Err(DecoderError::RlpInvalidLength)
+    }
+}
+
+fn to_transact_to(to: Option<Address>) -> TransactTo {
+    match to {
+        Some(address) => TransactTo::Call(address),
+        None => TransactTo::Create(CreateScheme::Create),
+    }
+}
+
+fn decode_access_list(rlp: &Rlp<'_>) -> Result<Vec<(Address, Vec<U256>)>, DecoderError> {
+    rlp.iter()
+        .map(|entry| {
+            let address_data = entry.val_at::<Vec<u8>>(0)?;
+            if address_data.len() != 20 {
+                return Err(DecoderError::RlpInvalidLength);
+            }
+            let address = Address::from_slice(&address_data);
+            let keys = entry
+                .at(1)?
+                .iter()
+                .map(|key| Ok(U256::from_be_slice(&key.data()?[..])))
+                .collect::<Result<Vec<_>, DecoderError>>()?;
+            Ok((address, keys))
+        })
+        .collect()
+}
+
+fn recover_caller(sighash: B256, v: u64, r: EthersU256, s: EthersU256) -> PyResult<Address> {
+    let signature = EthersSignature { r, s, v };
+    let recovered = signature
+        .recover(RecoveryMessage::Hash(sighash.0.into()))
+        .map_err(pyerr)?;
+    Ok(Address::from_slice(recovered.as_bytes()))
+}
+
+/// Legacy (pre-EIP-2718) transaction: `rlp([nonce, gasPrice, gasLimit, to, value, data, v, r, s])`.
+/// `v` carries EIP-155 replay protection (`v = chain_id * 2 + 35/36`) when present.
+fn decode_legacy(raw: &[u8]) -> PyResult<RevmTxEnv> {
+    let rlp = Rlp::new(raw);
+    let nonce: EthersU256 = rlp.val_at(0).map_err(pyerr)?;
+    let gas_price: EthersU256 = rlp.val_at(1).map_err(pyerr)?;
+    let gas_limit: EthersU256 = rlp.val_at(2).map_err(pyerr)?;
+    let to = to_address(&rlp, 3).map_err(pyerr)?;
+    let value: EthersU256 = rlp.val_at(4).map_err(pyerr)?;
+    let data: Vec<u8> = rlp.val_at(5).map_err(pyerr)?;
+    let v: u64 = rlp.val_at(6).map_err(pyerr)?;
+    let r: EthersU256 = rlp.val_at(7).map_err(pyerr)?;
+    let s: EthersU256 = rlp.val_at(8).map_err(pyerr)?;
+
+    let chain_id = if v >= 35 { Some((v - 35) / 2) } else { None };
+
+    let mut stream = ethers_core::utils::rlp::RlpStream::new();
+    match chain_id {
+        Some(id) => {
+            stream.begin_list(9);
+            stream.append(&nonce);
+            stream.append(&gas_price);
+            stream.append(&gas_limit);
+            match to {
+                Some(addr) => stream.append(&addr.as_slice()),
+                None => stream.append_empty_data(),
+            };
+            stream.append(&value);
+            stream.append(&data);
+            stream.append(&id);
+            stream.append(&0u8);
+            stream.append(&0u8);
+        }
+        None => {
+            stream.begin_list(6);
+            stream.append(&nonce);
+            stream.append(&gas_price);
+            stream.append(&gas_limit);
+            match to {
+                Some(addr) => stream.append(&addr.as_slice()),
+                None => stream.append_empty_data(),
+            };
+            stream.append(&value);
+            stream.append(&data);
+        }
+    }
+    let sighash = B256::from_slice(&keccak256(stream.out()));
+    let caller = recover_caller(sighash, v, r, s)?;
+
+    Ok(RevmTxEnv {
+        caller,
+        gas_limit: gas_limit.as_u64(),
+        gas_price: eu256_to_u256(gas_price),
+        gas_priority_fee: None,
+        transact_to: to_transact_to(to),
+        value: eu256_to_u256(value),
+        data: data.into(),
+        chain_id,
+        nonce: Some(nonce.as_u64()),
+        access_list: Vec::new(),
+        blob_hashes: Vec::new(),
+        max_fee_per_blob_gas: None,
+        #[cfg(feature = "optimism")]
+        optimism: Default::default(),
+    })
+}
+
+/// EIP-2930 payload: `[chainId, nonce, gasPrice, gasLimit, to, value, data, accessList, yParity, r, s]`.
+fn decode_eip2930(payload: &[u8]) -> PyResult<RevmTxEnv> {
+    let rlp = Rlp::new(payload);
+    let chain_id: u64 = rlp.val_at(0).map_err(pyerr)?;
+    let nonce: EthersU256 = rlp.val_at(1).map_err(pyerr)?;
+    let gas_price: EthersU256 = rlp.val_at(2).map_err(pyerr)?;
+    let gas_limit: EthersU256 = rlp.val_at(3).map_err(pyerr)?;
+    let to = to_address(&rlp, 4).map_err(pyerr)?;
+    let value: EthersU256 = rlp.val_at(5).map_err(pyerr)?;
+    let data: Vec<u8> = rlp.val_at(6).map_err(pyerr)?;
+    let access_list = decode_access_list(&rlp.at(7).map_err(pyerr)?).map_err(pyerr)?;
+    let y_parity: u64 = rlp.val_at(8).map_err(pyerr)?;
+    let r: EthersU256 = rlp.val_at(9).map_err(pyerr)?;
+    let s: EthersU256 = rlp.val_at(10).map_err(pyerr)?;
+
+    let mut stream = ethers_core::utils::rlp::RlpStream::new();
+    stream.begin_list(8);
+    stream.append(&chain_id);
+    stream.append(&nonce);
+    stream.append(&gas_price);
+    stream.append(&gas_limit);
+    match to {
+        Some(addr) => stream.append(&addr.as_slice()),
+        None => stream.append_empty_data(),
+    };
+    stream.append(&value);
+    stream.append(&data);
+    encode_access_list(&mut stream, &access_list);
+    let mut sighash_input = vec![0x01u8];
+    sighash_input.extend_from_slice(&stream.out());
+    let sighash = B256::from_slice(&keccak256(sighash_input));
+    let caller = recover_caller(sighash, y_parity, r, s)?;
+
+    Ok(RevmTxEnv {
+        caller,
+        gas_limit: gas_limit.as_u64(),
+        gas_price: eu256_to_u256(gas_price),
+        gas_priority_fee: None,
+        transact_to: to_transact_to(to),
+        value: eu256_to_u256(value),
+        data: data.into(),
+        chain_id: Some(chain_id),
+        nonce: Some(nonce.as_u64()),
+        access_list,
+        blob_hashes: Vec::new(),
+        max_fee_per_blob_gas: None,
+        #[cfg(feature = "optimism")]
+        optimism: Default::default(),
+    })
+}
+
+/// EIP-1559 payload:
+/// `[chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data, accessList, yParity, r, s]`.
+fn decode_eip1559(payload: &[u8]) -> PyResult<RevmTxEnv> {
+    let rlp = Rlp::new(payload);
+    let chain_id: u64 = rlp.val_at(0).map_err(pyerr)?;
+    let nonce: EthersU256 = rlp.val_at(1).map_err(pyerr)?;
+    let max_priority_fee: EthersU256 = rlp.val_at(2).map_err(pyerr)?;
+    let max_fee: EthersU256 = rlp.val_at(3).map_err(pyerr)?;
+    let gas_limit: EthersU256 = rlp.val_at(4).map_err(pyerr)?;
+    let to = to_address(&rlp, 5).map_err(pyerr)?;
+    let value: EthersU256 = rlp.val_at(6).map_err(pyerr)?;
+    let data: Vec<u8> = rlp.val_at(7).map_err(pyerr)?;
+    let access_list = decode_access_list(&rlp.at(8).map_err(pyerr)?).map_err(pyerr)?;
+    let y_parity: u64 = rlp.val_at(9).map_err(pyerr)?;
+    let r: EthersU256 = rlp.val_at(10).map_err(pyerr)?;
+    let s: EthersU256 = rlp.val_at(11).map_err(pyerr)?;
+
+    let mut stream = ethers_core::utils::rlp::RlpStream::new();
+    stream.begin_list(9);
+    stream.append(&chain_id);
+    stream.append(&nonce);
+    stream.append(&max_priority_fee);
+    stream.append(&max_fee);
+    stream.append(&gas_limit);
+    match to {
+        Some(addr) => stream.append(&addr.as_slice()),
+        None => stream.append_empty_data(),
+    };
+    stream.append(&value);
+    stream.append(&data);
+    encode_access_list(&mut stream, &access_list);
+    let mut sighash_input = vec![0x02u8];
+    sighash_input.extend_from_slice(&stream.out());
+    let sighash = B256::from_slice(&keccak256(sighash_input));
+    let caller = recover_caller(sighash, y_parity, r, s)?;
+
+    Ok(RevmTxEnv {
+        caller,
+        gas_limit: gas_limit.as_u64(),
+        gas_price: eu256_to_u256(max_fee),
+        gas_priority_fee: Some(eu256_to_u256(max_priority_fee)),
+        transact_to: to_transact_to(to),
+        value: eu256_to_u256(value),
+        data: data.into(),
+        chain_id: Some(chain_id),
+        nonce: Some(nonce.as_u64()),
+        access_list,
+        blob_hashes: Vec::new(),
+        max_fee_per_blob_gas: None,
+        #[cfg(feature = "optimism")]
+        optimism: Default::default(),
+    })
+}
+
+/// EIP-4844 payload: `[chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value,
+/// data, accessList, maxFeePerBlobGas, blobVersionedHashes, yParity, r, s]`. `to` is always a
+/// 20-byte address - blob transactions cannot create contracts.
+fn decode_eip4844(payload: &[u8]) -> PyResult<RevmTxEnv> {
+    let rlp = Rlp::new(payload);
+    let chain_id: u64 = rlp.val_at(0).map_err(pyerr)?;
+    let nonce: EthersU256 = rlp.val_at(1).map_err(pyerr)?;
+    let max_priority_fee: EthersU256 = rlp.val_at(2).map_err(pyerr)?;
+    let max_fee: EthersU256 = rlp.val_at(3).map_err(pyerr)?;
+    let gas_limit: EthersU256 = rlp.val_at(4).map_err(pyerr)?;
+    let to: H160 = rlp.val_at(5).map_err(pyerr)?;
+    let value: EthersU256 = rlp.val_at(6).map_err(pyerr)?;
+    let data: Vec<u8> = rlp.val_at(7).map_err(pyerr)?;
+    let access_list = decode_access_list(&rlp.at(8).map_err(pyerr)?).map_err(pyerr)?;
+    let max_fee_per_blob_gas: EthersU256 = rlp.val_at(9).map_err(pyerr)?;
+    let blob_hashes: Vec<B256> = rlp
+        .at(10)
+        .map_err(pyerr)?
+        .iter()
+        .map(|h| Ok(B256::from_slice(h.data()?)))
+        .collect::<Result<Vec<_>, DecoderError>>()
+        .map_err(pyerr)?;
+    let y_parity: u64 = rlp.val_at(11).map_err(pyerr)?;
+    let r: EthersU256 = rlp.val_at(12).map_err(pyerr)?;
+    let s: EthersU256 = rlp.val_at(13).map_err(pyerr)?;
+
+    let mut stream = ethers_core::utils::rlp::RlpStream::new();
+    stream.begin_list(11);
+    stream.append(&chain_id);
+    stream.append(&nonce);
+    stream.append(&max_priority_fee);
+    stream.append(&max_fee);
+    stream.append(&gas_limit);
+    stream.append(&to.0.as_slice());
+    stream.append(&value);
+    stream.append(&data);
+    encode_access_list(&mut stream, &access_list);
+    stream.append(&max_fee_per_blob_gas);
+    stream.begin_list(blob_hashes.len());
+    for hash in &blob_hashes {
+        stream.append(&hash.as_slice());
+    }
+    let mut sighash_input = vec![0x03u8];
+    sighash_input.extend_from_slice(&stream.out());
+    let sighash = B256::from_slice(&keccak256(sighash_input));
+    let caller = recover_caller(sighash, y_parity, r, s)?;
+
+    Ok(RevmTxEnv {
+        caller,
+        gas_limit: gas_limit.as_u64(),
+        gas_price: eu256_to_u256(max_fee),
+        gas_priority_fee: Some(eu256_to_u256(max_priority_fee)),
+        transact_to: TransactTo::Call(Address::from_slice(to.as_bytes())),
+        value: eu256_to_u256(value),
+        data: data.into(),
+        chain_id: Some(chain_id),
+        nonce: Some(nonce.as_u64()),
+        access_list,
+        blob_hashes,
+        max_fee_per_blob_gas: Some(eu256_to_u256(max_fee_per_blob_gas)),
+        #[cfg(feature = "optimism")]
+        optimism: Default::default(),
+    })
+}
+
+fn encode_access_list(
+    stream: &mut ethers_core::utils::rlp::RlpStream,
+    access_list: &[(Address, Vec<U256>)],
+) {
+    stream.begin_list(access_list.len());
+    for (address, keys) in access_list {
+        stream.begin_list(2);
+        stream.append(&address.as_slice());
+        stream.begin_list(keys.len());
+        for key in keys {
+            stream.append(&key.to_be_bytes_vec().as_slice());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers_core::utils::rlp::RlpStream;
+
+    use super::*;
+
+    // `r` is the secp256k1 generator's x-coordinate, which is guaranteed to be a valid curve
+    // x-coordinate - so together with a minimal `s`/`v` it always produces a well-formed (if not
+    // meaningful) ECDSA recovery, letting these tests exercise field decoding end-to-end.
+    const R: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    fn sig_r() -> EthersU256 {
+        EthersU256::from_str_radix(R, 16).unwrap()
+    }
+
+    fn append_address_or_empty(stream: &mut RlpStream, address: Option<Address>) {
+        match address {
+            Some(address) => {
+                stream.append(&address.as_slice());
+            }
+            None => {
+                stream.append_empty_data();
+            }
+        };
+    }
+
+    #[test]
+    fn decode_legacy_roundtrips_fields() {
+        let to = Address::repeat_byte(0x11);
+        let data = vec![0xde, 0xad, 0xbe, 0xef];
+
+        let mut stream = RlpStream::new();
+        stream.begin_list(9);
+        stream.append(&42u64);
+        stream.append(&1_000_000_000u64);
+        stream.append(&21_000u64);
+        append_address_or_empty(&mut stream, Some(to));
+        stream.append(&7u64);
+        stream.append(&data);
+        stream.append(&27u8);
+        stream.append(&sig_r());
+        stream.append(&1u8);
+
+        let tx = decode_raw_tx(&stream.out()).unwrap();
+        assert_eq!(tx.nonce, Some(42));
+        assert_eq!(tx.gas_limit, 21_000);
+        assert_eq!(tx.gas_price, U256::from(1_000_000_000u64));
+        assert_eq!(tx.transact_to, TransactTo::Call(to));
+        assert_eq!(tx.value, U256::from(7));
+        assert_eq!(tx.data.as_ref(), data.as_slice());
+        assert_eq!(tx.chain_id, None);
+    }
+
+    #[test]
+    fn decode_legacy_create_has_no_to() {
+        let mut stream = RlpStream::new();
+        stream.begin_list(9);
+        stream.append(&0u64);
+        stream.append(&1u64);
+        stream.append(&21_000u64);
+        append_address_or_empty(&mut stream, None);
+        stream.append(&0u64);
+        stream.append(&Vec::<u8>::new());
+        stream.append(&27u8);
+        stream.append(&sig_r());
+        stream.append(&1u8);
+
+        let tx = decode_raw_tx(&stream.out()).unwrap();
+        assert_eq!(tx.transact_to, TransactTo::Create(CreateScheme::Create));
+    }
+
+    #[test]
+    fn decode_eip2930_roundtrips_fields() {
+        let to = Address::repeat_byte(0x22);
+        let access_address = Address::repeat_byte(0x33);
+
+        let mut stream = RlpStream::new();
+        stream.begin_list(11);
+        stream.append(&5u64);
+        stream.append(&1u64);
+        stream.append(&2u64);
+        stream.append(&21_000u64);
+        append_address_or_empty(&mut stream, Some(to));
+        stream.append(&0u64);
+        stream.append(&Vec::<u8>::new());
+        encode_access_list(&mut stream, &[(access_address, vec![U256::from(9)])]);
+        stream.append(&0u8);
+        stream.append(&sig_r());
+        stream.append(&1u8);
+
+        let mut raw = vec![0x01u8];
+        raw.extend_from_slice(&stream.out());
+
+        let tx = decode_raw_tx(&raw).unwrap();
+        assert_eq!(tx.chain_id, Some(5));
+        assert_eq!(tx.access_list, vec![(access_address, vec![U256::from(9)])]);
+        assert_eq!(tx.transact_to, TransactTo::Call(to));
+    }
+
+    #[test]
+    fn decode_eip1559_roundtrips_fields() {
+        let to = Address::repeat_byte(0x44);
+
+        let mut stream = RlpStream::new();
+        stream.begin_list(12);
+        stream.append(&1u64);
+        stream.append(&3u64);
+        stream.append(&1_000u64);
+        stream.append(&2_000u64);
+        stream.append(&21_000u64);
+        append_address_or_empty(&mut stream, Some(to));
+        stream.append(&0u64);
+        stream.append(&Vec::<u8>::new());
+        encode_access_list(&mut stream, &[]);
+        stream.append(&1u8);
+        stream.append(&sig_r());
+        stream.append(&1u8);
+
+        let mut raw = vec![0x02u8];
+        raw.extend_from_slice(&stream.out());
+
+        let tx = decode_raw_tx(&raw).unwrap();
+        assert_eq!(tx.gas_price, U256::from(2_000u64));
+        assert_eq!(tx.gas_priority_fee, Some(U256::from(1_000u64)));
+        assert_eq!(tx.transact_to, TransactTo::Call(to));
+    }
+
+    #[test]
+    fn decode_eip4844_roundtrips_fields() {
+        let to = Address::repeat_byte(0x55);
+        let blob_hash = B256::repeat_byte(0x66);
+
+        let mut stream = RlpStream::new();
+        stream.begin_list(14);
+        stream.append(&1u64);
+        stream.append(&9u64);
+        stream.append(&1_000u64);
+        stream.append(&2_000u64);
+        stream.append(&21_000u64);
+        stream.append(&to.as_slice());
+        stream.append(&0u64);
+        stream.append(&Vec::<u8>::new());
+        encode_access_list(&mut stream, &[]);
+        stream.append(&3_000u64);
+        stream.begin_list(1);
+        stream.append(&blob_hash.as_slice());
+        stream.append(&1u8);
+        stream.append(&sig_r());
+        stream.append(&1u8);
+
+        let mut raw = vec![0x03u8];
+        raw.extend_from_slice(&stream.out());
+
+        let tx = decode_raw_tx(&raw).unwrap();
+        assert_eq!(tx.max_fee_per_blob_gas, Some(U256::from(3_000u64)));
+        assert_eq!(tx.blob_hashes, vec![blob_hash]);
+        assert_eq!(tx.transact_to, TransactTo::Call(to));
+    }
+
+    #[test]
+    fn to_address_rejects_wrong_length() {
+        let mut stream = RlpStream::new();
+        stream.begin_list(1);
+        stream.append(&vec![0u8; 19]);
+        let rlp = Rlp::new(&stream.out());
+        assert!(to_address(&rlp, 0).is_err());
+    }
+
+    #[test]
+    fn decode_access_list_rejects_wrong_length_address() {
+        let mut entries = RlpStream::new();
+        entries.begin_list(1);
+        entries.begin_list(2);
+        entries.append(&vec![0u8; 19]);
+        entries.begin_list(0);
+        let rlp = Rlp::new(&entries.out());
+        assert!(decode_access_list(&rlp).is_err());
+    }
+}
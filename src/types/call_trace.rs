@@ -0,0 +1,20 @@
+use pyo3::pyclass;
+use revm::primitives::U256;
+
+use super::Log;
+
+#[pyclass(get_all)]
+#[derive(Debug, Clone, Default)]
+pub struct CallFrame {
+    pub kind: String,
+    pub from: String,
+    pub to: String,
+    pub input: Vec<u8>,
+    pub value: U256,
+    pub gas: u64,
+    pub gas_used: u64,
+    pub output: Vec<u8>,
+    pub revert_reason: Option<String>,
+    pub logs: Vec<Log>,
+    pub calls: Vec<CallFrame>,
+}
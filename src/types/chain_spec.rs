@@ -0,0 +1,194 @@
+use std::fs;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::PyResult;
+use revm::primitives::{SpecId, U256};
+use serde::Deserialize;
+
+use crate::utils::pyerr;
+
+#[derive(Deserialize)]
+pub(crate) struct ChainSpec {
+    pub(crate) params: ChainSpecParams,
+    pub(crate) genesis: ChainSpecGenesis,
+}
+
+#[derive(Deserialize, Default)]
+pub(crate) struct ChainSpecParams {
+    #[serde(
+        rename = "chainID",
+        alias = "chainId",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) chain_id: Option<u64>,
+    #[serde(
+        rename = "homesteadTransition",
+        alias = "homesteadBlock",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) homestead_block: Option<u64>,
+    #[serde(
+        rename = "eip150Transition",
+        alias = "eip150Block",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) tangerine_whistle_block: Option<u64>,
+    #[serde(
+        rename = "eip160Transition",
+        alias = "eip158Block",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) spurious_dragon_block: Option<u64>,
+    #[serde(
+        rename = "byzantiumTransition",
+        alias = "byzantiumBlock",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) byzantium_block: Option<u64>,
+    #[serde(
+        rename = "constantinopleTransition",
+        alias = "constantinopleBlock",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) constantinople_block: Option<u64>,
+    #[serde(
+        rename = "petersburgTransition",
+        alias = "petersburgBlock",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) petersburg_block: Option<u64>,
+    #[serde(
+        rename = "istanbulTransition",
+        alias = "istanbulBlock",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) istanbul_block: Option<u64>,
+    #[serde(
+        rename = "berlinTransition",
+        alias = "berlinBlock",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) berlin_block: Option<u64>,
+    #[serde(
+        rename = "londonTransition",
+        alias = "londonBlock",
+        default,
+        deserialize_with = "de_hex_u64"
+    )]
+    pub(crate) london_block: Option<u64>,
+}
+
+#[derive(Deserialize, Default)]
+pub(crate) struct ChainSpecGenesis {
+    #[serde(default, deserialize_with = "de_hex_u256")]
+    pub(crate) difficulty: Option<U256>,
+    #[serde(default)]
+    pub(crate) author: Option<String>,
+    #[serde(default, deserialize_with = "de_hex_u256")]
+    pub(crate) timestamp: Option<U256>,
+    #[serde(rename = "gasLimit", default, deserialize_with = "de_hex_u256")]
+    pub(crate) gas_limit: Option<U256>,
+    #[serde(default, deserialize_with = "de_hex_u256")]
+    pub(crate) number: Option<U256>,
+}
+
+fn de_hex_u64<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    Ok(value.and_then(|v| match v {
+        serde_json::Value::String(s) => u64::from_str_radix(s.trim_start_matches("0x"), 16).ok(),
+        serde_json::Value::Number(n) => n.as_u64(),
+        _ => None,
+    }))
+}
+
+fn de_hex_u256<'de, D>(deserializer: D) -> Result<Option<U256>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    Ok(value.and_then(|s| U256::from_str_radix(s.trim_start_matches("0x"), 16).ok()))
+}
+
+/// Reads and parses the chainspec JSON at `path`.
+pub(crate) fn load_chain_spec(path: &str) -> PyResult<ChainSpec> {
+    let json = fs::read_to_string(path).map_err(pyerr)?;
+    serde_json::from_str(&json).map_err(|e| PyValueError::new_err(format!("Invalid chain spec: {e}")))
+}
+
+/// Resolves the highest hardfork whose transition block is at or below `at_block`.
+pub(crate) fn resolve_spec_id(params: &ChainSpecParams, at_block: u64) -> SpecId {
+    let activated = |block: Option<u64>| block.is_some_and(|b| at_block >= b);
+    if activated(params.london_block) {
+        SpecId::LONDON
+    } else if activated(params.berlin_block) {
+        SpecId::BERLIN
+    } else if activated(params.istanbul_block) {
+        SpecId::ISTANBUL
+    } else if activated(params.petersburg_block) {
+        SpecId::PETERSBURG
+    } else if activated(params.constantinople_block) {
+        SpecId::CONSTANTINOPLE
+    } else if activated(params.byzantium_block) {
+        SpecId::BYZANTIUM
+    } else if activated(params.spurious_dragon_block) {
+        SpecId::SPURIOUS_DRAGON
+    } else if activated(params.tangerine_whistle_block) {
+        SpecId::TANGERINE
+    } else if activated(params.homestead_block) {
+        SpecId::HOMESTEAD
+    } else {
+        SpecId::FRONTIER
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_spec_id_picks_highest_activated_fork() {
+        let params = ChainSpecParams {
+            homestead_block: Some(0),
+            byzantium_block: Some(10),
+            london_block: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(resolve_spec_id(&params, 0), SpecId::HOMESTEAD);
+        assert_eq!(resolve_spec_id(&params, 10), SpecId::BYZANTIUM);
+        assert_eq!(resolve_spec_id(&params, 50), SpecId::BYZANTIUM);
+        assert_eq!(resolve_spec_id(&params, 100), SpecId::LONDON);
+    }
+
+    #[test]
+    fn resolve_spec_id_defaults_to_frontier() {
+        assert_eq!(
+            resolve_spec_id(&ChainSpecParams::default(), 1_000_000),
+            SpecId::FRONTIER
+        );
+    }
+
+    #[test]
+    fn deserializes_hex_and_decimal_transition_blocks() {
+        let json = r#"{
+            "params": {"chainID": "0x1", "londonTransition": 100},
+            "genesis": {"difficulty": "0x20000", "gasLimit": "0x1388", "author": "0x0000000000000000000000000000000000000001"}
+        }"#;
+        let spec: ChainSpec = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.params.chain_id, Some(1));
+        assert_eq!(spec.params.london_block, Some(100));
+        assert_eq!(spec.genesis.difficulty, Some(U256::from(0x20000u64)));
+        assert_eq!(spec.genesis.gas_limit, Some(U256::from(0x1388u64)));
+    }
+}
@@ -1,14 +1,27 @@
 use std::default::Default;
 
+use pyo3::exceptions::PyValueError;
 use pyo3::types::PyTuple;
 use pyo3::{pyclass, pymethods, types::PyBytes, PyObject, PyResult, Python};
 use revm::primitives::{
     Address, BlobExcessGasAndPrice, BlockEnv as RevmBlockEnv, CfgEnv as RevmCfgEnv, CreateScheme,
-    Env as RevmEnv, TransactTo, TxEnv as RevmTxEnv, B256, U256,
+    Env as RevmEnv, SpecId, TransactTo, TxEnv as RevmTxEnv, B256, U256,
 };
+use serde::{de::DeserializeOwned, Serialize};
 
+use super::chain_spec::{load_chain_spec, resolve_spec_id};
 use crate::utils::{addr, addr_or_zero, from_pybytes};
 
+/// Serializes a revm env type to a stable JSON document, for `to_json`/`__getstate__`.
+fn to_json<T: Serialize>(value: &T) -> PyResult<String> {
+    serde_json::to_string(value).map_err(crate::utils::pyerr)
+}
+
+/// Deserializes a revm env type from JSON, for `from_json`/`__setstate__`.
+fn from_json<T: DeserializeOwned>(json: &str) -> PyResult<T> {
+    serde_json::from_str(json).map_err(|e| PyValueError::new_err(format!("Invalid JSON: {e}")))
+}
+
 #[pyclass]
 #[derive(Clone, Debug, Default)]
 pub struct Env(RevmEnv);
@@ -39,6 +52,27 @@ impl Env {
         self.0.tx.clone().into()
     }
 
+    /// Serializes this environment to a stable JSON document (addresses as hex strings,
+    /// `U256`/bytes as hex, access list as nested arrays). Round-trips via `Env.from_json`.
+    fn to_json(&self) -> PyResult<String> {
+        to_json(&self.0)
+    }
+
+    /// Reconstructs an `Env` from a document produced by `to_json`.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        Ok(Env(from_json(json)?))
+    }
+
+    fn __getstate__(&self) -> PyResult<String> {
+        self.to_json()
+    }
+
+    fn __setstate__(&mut self, state: String) -> PyResult<()> {
+        self.0 = from_json(&state)?;
+        Ok(())
+    }
+
     fn __str__(&self) -> PyResult<String> {
         Ok(format!("{:?}", self))
     }
@@ -63,6 +97,7 @@ pub struct TxEnv(pub RevmTxEnv);
 #[pymethods]
 impl TxEnv {
     #[new]
+    #[pyo3(signature = (caller = None, gas_limit = None, gas_price = None, gas_priority_fee = None, to = None, value = None, data = None, chain_id = None, nonce = None, salt = None, access_list = None, blob_hashes = None, max_fee_per_blob_gas = None, mint = None, source_hash = None, is_system_transaction = None))]
     pub fn new(
         caller: Option<&str>,
         gas_limit: Option<u64>,
@@ -77,6 +112,9 @@ impl TxEnv {
         access_list: Option<Vec<&PyTuple /*str, list[int]*/>>,
         blob_hashes: Option<Vec<&PyBytes>>,
         max_fee_per_blob_gas: Option<U256>,
+        mint: Option<u128>,
+        source_hash: Option<&PyBytes>,
+        is_system_transaction: Option<bool>,
     ) -> PyResult<Self> {
         Ok(TxEnv(RevmTxEnv {
             caller: addr_or_zero(caller)?,
@@ -109,6 +147,13 @@ impl TxEnv {
                 .map(|b| from_pybytes(b))
                 .collect::<PyResult<Vec<B256>>>()?,
             max_fee_per_blob_gas,
+            #[cfg(feature = "optimism")]
+            optimism: revm::primitives::OptimismFields {
+                source_hash: source_hash.map(from_pybytes).transpose()?,
+                mint,
+                is_system_transaction,
+                ..Default::default()
+            },
         }))
     }
 
@@ -191,6 +236,64 @@ impl TxEnv {
         self.0.max_fee_per_blob_gas
     }
 
+    /// The amount to mint into the caller's balance before execution, for an Optimism
+    /// deposit transaction.
+    #[cfg(feature = "optimism")]
+    #[getter]
+    fn mint(&self) -> Option<u128> {
+        self.0.optimism.mint
+    }
+
+    /// The L1 source hash of an Optimism deposit transaction.
+    #[cfg(feature = "optimism")]
+    #[getter]
+    fn source_hash(&self, py: Python<'_>) -> Option<PyObject> {
+        self.0
+            .optimism
+            .source_hash
+            .map(|h| PyBytes::new(py, h.as_ref()).into())
+    }
+
+    /// Whether this is an Optimism system transaction (no gas charged pre-Regolith).
+    #[cfg(feature = "optimism")]
+    #[getter]
+    fn is_system_transaction(&self) -> Option<bool> {
+        self.0.optimism.is_system_transaction
+    }
+
+    #[cfg(feature = "optimism")]
+    #[setter]
+    fn set_mint(&mut self, mint: Option<u128>) {
+        self.0.optimism.mint = mint;
+    }
+
+    #[cfg(feature = "optimism")]
+    #[setter]
+    fn set_source_hash(&mut self, source_hash: Option<&PyBytes>) -> PyResult<()> {
+        self.0.optimism.source_hash = source_hash.map(from_pybytes).transpose()?;
+        Ok(())
+    }
+
+    #[cfg(feature = "optimism")]
+    #[setter]
+    fn set_is_system_transaction(&mut self, is_system_transaction: Option<bool>) {
+        self.0.optimism.is_system_transaction = is_system_transaction;
+    }
+
+    #[setter]
+    fn set_access_list(&mut self, access_list: Vec<&PyTuple /*str, list[int]*/>) -> PyResult<()> {
+        self.0.access_list = access_list
+            .iter()
+            .map(|tuple| {
+                Ok((
+                    addr(tuple.get_item(0)?.extract()?)?,
+                    tuple.get_item(1)?.extract::<Vec<U256>>()?,
+                ))
+            })
+            .collect::<PyResult<Vec<(Address, Vec<U256>)>>>()?;
+        Ok(())
+    }
+
     #[setter]
     fn set_blob_hashes(&mut self, blob_hashes: Vec<&PyBytes>) -> PyResult<()> {
         self.0.blob_hashes = blob_hashes
@@ -205,6 +308,35 @@ impl TxEnv {
         self.0.max_fee_per_blob_gas = max_fee_per_blob_gas;
     }
 
+    /// Serializes this transaction environment to a stable JSON document (addresses as hex
+    /// strings, `U256`/bytes/blob hashes as hex, access list as nested arrays). Round-trips via
+    /// `TxEnv.from_json`.
+    fn to_json(&self) -> PyResult<String> {
+        to_json(&self.0)
+    }
+
+    /// Reconstructs a `TxEnv` from a document produced by `to_json`.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        Ok(TxEnv(from_json(json)?))
+    }
+
+    fn __getstate__(&self) -> PyResult<String> {
+        self.to_json()
+    }
+
+    fn __setstate__(&mut self, state: String) -> PyResult<()> {
+        self.0 = from_json(&state)?;
+        Ok(())
+    }
+
+    /// Decodes a signed, RLP-encoded transaction - legacy, or an EIP-2718 typed envelope
+    /// (EIP-2930, EIP-1559, EIP-4844) - into a `TxEnv`, recovering `caller` from the signature.
+    #[staticmethod]
+    fn from_raw(raw: &PyBytes) -> PyResult<Self> {
+        Ok(TxEnv(crate::tx_decode::decode_raw_tx(raw.as_bytes())?))
+    }
+
     fn __str__(&self) -> PyResult<String> {
         Ok(format!("{:?}", self))
     }
@@ -324,6 +456,46 @@ impl BlockEnv {
         self.0.blob_excess_gas_and_price = excess_blob_gas.map(BlobExcessGasAndPrice::new);
     }
 
+    /// Parses a genesis/chainspec JSON at `path` and maps its `genesis` block onto a starting
+    /// `BlockEnv` (number, timestamp, difficulty, gas_limit, coinbase). Companion to
+    /// `CfgEnv.from_chain_spec`.
+    #[staticmethod]
+    fn from_chain_spec(path: &str) -> PyResult<Self> {
+        let spec = load_chain_spec(path)?;
+        let genesis = spec.genesis;
+        Ok(BlockEnv(RevmBlockEnv {
+            number: genesis.number.unwrap_or_default(),
+            coinbase: addr_or_zero(genesis.author.as_deref())?,
+            timestamp: genesis.timestamp.unwrap_or(U256::from(1)),
+            difficulty: genesis.difficulty.unwrap_or_default(),
+            prevrandao: Some(B256::ZERO),
+            basefee: U256::ZERO,
+            gas_limit: genesis.gas_limit.unwrap_or_else(|| U256::from(u64::MAX)),
+            blob_excess_gas_and_price: Some(BlobExcessGasAndPrice::new(0)),
+        }))
+    }
+
+    /// Serializes this block environment to a stable JSON document (`coinbase` as a hex string,
+    /// `U256`/`prevrandao` as hex). Round-trips via `BlockEnv.from_json`.
+    fn to_json(&self) -> PyResult<String> {
+        to_json(&self.0)
+    }
+
+    /// Reconstructs a `BlockEnv` from a document produced by `to_json`.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        Ok(BlockEnv(from_json(json)?))
+    }
+
+    fn __getstate__(&self) -> PyResult<String> {
+        self.to_json()
+    }
+
+    fn __setstate__(&mut self, state: String) -> PyResult<()> {
+        self.0 = from_json(&state)?;
+        Ok(())
+    }
+
     fn __str__(&self) -> PyResult<String> {
         Ok(format!("{:?}", self))
     }
@@ -347,9 +519,142 @@ pub struct CfgEnv(RevmCfgEnv);
 
 #[pymethods]
 impl CfgEnv {
+    /// `spec_id` selects the active hardfork, e.g. `"FRONTIER"`, `"HOMESTEAD"`, `"LONDON"`,
+    /// `"SHANGHAI"`, `"CANCUN"` - anything accepted by revm's `SpecId::from(&str)`.
     #[new]
-    fn new() -> Self {
-        CfgEnv(RevmCfgEnv::default())
+    #[pyo3(signature = (spec_id = "LATEST", chain_id = None, limit_contract_code_size = None))]
+    fn new(spec_id: &str, chain_id: Option<u64>, limit_contract_code_size: Option<usize>) -> Self {
+        let mut cfg = RevmCfgEnv {
+            spec_id: SpecId::from(spec_id),
+            limit_contract_code_size,
+            ..Default::default()
+        };
+        if let Some(chain_id) = chain_id {
+            cfg.chain_id = chain_id;
+        }
+        CfgEnv(cfg)
+    }
+
+    #[getter]
+    fn spec_id(&self) -> String {
+        format!("{:?}", self.0.spec_id)
+    }
+
+    #[setter]
+    fn set_spec_id(&mut self, spec_id: &str) {
+        self.0.spec_id = SpecId::from(spec_id);
+    }
+
+    #[getter]
+    fn chain_id(&self) -> u64 {
+        self.0.chain_id
+    }
+
+    #[setter]
+    fn set_chain_id(&mut self, chain_id: u64) {
+        self.0.chain_id = chain_id;
+    }
+
+    #[getter]
+    fn limit_contract_code_size(&self) -> Option<usize> {
+        self.0.limit_contract_code_size
+    }
+
+    #[setter]
+    fn set_limit_contract_code_size(&mut self, limit_contract_code_size: Option<usize>) {
+        self.0.limit_contract_code_size = limit_contract_code_size;
+    }
+
+    /// Whether the caller's balance check (and the corresponding deduction) is skipped.
+    #[getter]
+    fn disable_balance_check(&self) -> bool {
+        self.0.disable_balance_check
+    }
+
+    #[setter]
+    fn set_disable_balance_check(&mut self, disable: bool) {
+        self.0.disable_balance_check = disable;
+    }
+
+    /// Whether EIP-1559 base fee validation is skipped.
+    #[getter]
+    fn disable_base_fee(&self) -> bool {
+        self.0.disable_base_fee
+    }
+
+    #[setter]
+    fn set_disable_base_fee(&mut self, disable: bool) {
+        self.0.disable_base_fee = disable;
+    }
+
+    /// Whether the check that a transaction's `gas_limit` doesn't exceed the block's gas limit
+    /// is skipped.
+    #[getter]
+    fn disable_block_gas_limit(&self) -> bool {
+        self.0.disable_block_gas_limit
+    }
+
+    #[setter]
+    fn set_disable_block_gas_limit(&mut self, disable: bool) {
+        self.0.disable_block_gas_limit = disable;
+    }
+
+    /// Whether EIP-3607 (rejecting transactions whose sender has contract code) is skipped.
+    #[getter]
+    fn disable_eip3607(&self) -> bool {
+        self.0.disable_eip3607
+    }
+
+    #[setter]
+    fn set_disable_eip3607(&mut self, disable: bool) {
+        self.0.disable_eip3607 = disable;
+    }
+
+    /// Whether the caller's nonce check is skipped.
+    #[getter]
+    fn disable_nonce_check(&self) -> bool {
+        self.0.disable_nonce_check
+    }
+
+    #[setter]
+    fn set_disable_nonce_check(&mut self, disable: bool) {
+        self.0.disable_nonce_check = disable;
+    }
+
+    /// Parses a genesis/chainspec JSON at `path` (the `params`/`genesis` shape used by
+    /// go-ethereum's `genesis.json` and OpenEthereum's `frontier.json`/`homestead_test.json`)
+    /// and resolves the `chain_id` and the highest hardfork whose transition block is at or
+    /// below `at_block`.
+    #[staticmethod]
+    #[pyo3(signature = (path, at_block = 0))]
+    fn from_chain_spec(path: &str, at_block: u64) -> PyResult<Self> {
+        let spec = load_chain_spec(path)?;
+        Ok(CfgEnv(RevmCfgEnv {
+            chain_id: spec.params.chain_id.unwrap_or(1),
+            spec_id: resolve_spec_id(&spec.params, at_block),
+            ..Default::default()
+        }))
+    }
+
+    /// Serializes this config environment to a stable JSON document. Round-trips via
+    /// `CfgEnv.from_json`.
+    fn to_json(&self) -> PyResult<String> {
+        to_json(&self.0)
+    }
+
+    /// Reconstructs a `CfgEnv` from a document produced by `to_json`.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        Ok(CfgEnv(from_json(json)?))
+    }
+
+    fn __getstate__(&self) -> PyResult<String> {
+        self.to_json()
+    }
+
+    fn __setstate__(&mut self, state: String) -> PyResult<()> {
+        self.0 = from_json(&state)?;
+        Ok(())
     }
 
     fn __str__(&self) -> PyResult<String> {
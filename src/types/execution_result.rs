@@ -1,6 +1,8 @@
 use pyo3::types::PyBytes;
 use pyo3::{pyclass, pymethods, PyObject, Python};
-use revm::primitives::{ExecutionResult as RevmExecutionResult, Log as RevmLog};
+use revm::primitives::{
+    ExecutionResult as RevmExecutionResult, HaltReason as RevmHaltReason, Log as RevmLog,
+};
 
 #[derive(Debug, Clone, Hash)]
 #[pyclass]
@@ -32,6 +34,70 @@ impl Log {
     }
 }
 
+/// The reason a transaction halted, as a fixed, typed vocabulary rather than a stringified
+/// `Debug` dump of `revm::primitives::HaltReason`.
+#[pyclass(eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HaltReason {
+    OutOfGas,
+    OpcodeNotFound,
+    InvalidFEOpcode,
+    InvalidJump,
+    NotActivated,
+    StackUnderflow,
+    StackOverflow,
+    OutOfOffset,
+    CreateCollision,
+    PrecompileError,
+    NonceOverflow,
+    CreateContractSizeLimit,
+    CreateContractStartingWithEF,
+    CreateInitCodeSizeLimit,
+    OverflowPayment,
+    StateChangeDuringStaticCall,
+    CallNotAllowedInsideStatic,
+    OutOfFunds,
+    CallTooDeep,
+    #[cfg(feature = "optimism")]
+    FailedDeposit,
+    /// A halt reason not covered by the variants above.
+    Other,
+}
+
+impl From<&RevmHaltReason> for HaltReason {
+    fn from(reason: &RevmHaltReason) -> Self {
+        match reason {
+            RevmHaltReason::OutOfGas(_) => HaltReason::OutOfGas,
+            RevmHaltReason::OpcodeNotFound => HaltReason::OpcodeNotFound,
+            RevmHaltReason::InvalidFEOpcode => HaltReason::InvalidFEOpcode,
+            RevmHaltReason::InvalidJump => HaltReason::InvalidJump,
+            RevmHaltReason::NotActivated => HaltReason::NotActivated,
+            RevmHaltReason::StackUnderflow => HaltReason::StackUnderflow,
+            RevmHaltReason::StackOverflow => HaltReason::StackOverflow,
+            RevmHaltReason::OutOfOffset => HaltReason::OutOfOffset,
+            RevmHaltReason::CreateCollision => HaltReason::CreateCollision,
+            RevmHaltReason::PrecompileError => HaltReason::PrecompileError,
+            RevmHaltReason::NonceOverflow => HaltReason::NonceOverflow,
+            RevmHaltReason::CreateContractSizeLimit => HaltReason::CreateContractSizeLimit,
+            RevmHaltReason::CreateContractStartingWithEF => {
+                HaltReason::CreateContractStartingWithEF
+            }
+            RevmHaltReason::CreateInitCodeSizeLimit => HaltReason::CreateInitCodeSizeLimit,
+            RevmHaltReason::OverflowPayment => HaltReason::OverflowPayment,
+            RevmHaltReason::StateChangeDuringStaticCall => {
+                HaltReason::StateChangeDuringStaticCall
+            }
+            RevmHaltReason::CallNotAllowedInsideStatic => HaltReason::CallNotAllowedInsideStatic,
+            RevmHaltReason::OutOfFunds => HaltReason::OutOfFunds,
+            RevmHaltReason::CallTooDeep => HaltReason::CallTooDeep,
+            #[cfg(feature = "optimism")]
+            RevmHaltReason::FailedDeposit => HaltReason::FailedDeposit,
+            #[allow(unreachable_patterns)]
+            _ => HaltReason::Other,
+        }
+    }
+}
+
 /// Result of a transaction execution.
 #[derive(Debug, Clone, Hash)]
 #[pyclass(get_all)]
@@ -42,6 +108,11 @@ pub struct ExecutionResult {
     gas_used: u64,
     gas_refunded: u64,
     logs: Vec<Log>,
+    /// The returned/revert output, if any. Populated for `Success` (the call's return data) and
+    /// `Revert` (the revert payload, e.g. an ABI-encoded `Error(string)` or custom error).
+    output: Option<Vec<u8>>,
+    /// The structured halt reason, set only when `is_halt` is `true`.
+    halt_reason: Option<HaltReason>,
 }
 
 #[pymethods]
@@ -52,7 +123,7 @@ impl From<RevmExecutionResult> for ExecutionResult {
         ExecutionResult {
             is_success: result.is_success(),
             is_halt: result.is_halt(),
-            reason: match result {
+            reason: match &result {
                 RevmExecutionResult::Success { reason, .. } => format!("{:?}", reason),
                 RevmExecutionResult::Revert { .. } => String::from("Revert"),
                 RevmExecutionResult::Halt { reason, .. } => format!("{:?}", reason),
@@ -66,10 +137,21 @@ impl From<RevmExecutionResult> for ExecutionResult {
                 RevmExecutionResult::Success { gas_refunded, .. } => gas_refunded,
                 _ => u64::default(),
             },
-            logs: match result {
-                RevmExecutionResult::Success { logs, .. } => logs.into_iter().map(Log).collect(),
+            logs: match &result {
+                RevmExecutionResult::Success { logs, .. } => {
+                    logs.clone().into_iter().map(Log).collect()
+                }
                 _ => Vec::new(),
             },
+            output: match &result {
+                RevmExecutionResult::Success { output, .. } => Some(output.clone().into_data().to_vec()),
+                RevmExecutionResult::Revert { output, .. } => Some(output.to_vec()),
+                RevmExecutionResult::Halt { .. } => None,
+            },
+            halt_reason: match &result {
+                RevmExecutionResult::Halt { reason, .. } => Some(HaltReason::from(reason)),
+                _ => None,
+            },
         }
     }
 }
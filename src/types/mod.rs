@@ -1,5 +1,10 @@
 use std::collections::HashMap;
 
+mod call_trace;
+pub use call_trace::*;
+
+mod chain_spec;
+
 mod checkpoint;
 pub use checkpoint::*;
 
@@ -12,6 +17,15 @@ pub use execution_result::*;
 mod info;
 pub use info::*;
 
+mod overrides;
+pub use overrides::*;
+
+mod state_diff;
+pub use state_diff::*;
+
+mod trace_step;
+pub use trace_step::*;
+
 // In Py03 we use vec<u8> to represent bytes
 pub(crate) type PyByteVec = Vec<u8>;
 pub(crate) type PyDB = HashMap<String, AccountInfo>;
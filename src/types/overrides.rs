@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use pyo3::types::PyBytes;
+use pyo3::{pyclass, pymethods};
+use revm::primitives::U256;
+
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct AccountOverride {
+    pub balance: Option<U256>,
+    pub nonce: Option<u64>,
+    pub code: Option<Vec<u8>>,
+    pub storage: HashMap<U256, U256>,
+    pub storage_reset: bool,
+}
+
+#[pymethods]
+impl AccountOverride {
+    #[new]
+    #[pyo3(signature = (balance = None, nonce = None, code = None, storage = None, storage_reset = false))]
+    fn new(
+        balance: Option<U256>,
+        nonce: Option<u64>,
+        code: Option<&PyBytes>,
+        storage: Option<HashMap<U256, U256>>,
+        storage_reset: bool,
+    ) -> Self {
+        Self {
+            balance,
+            nonce,
+            code: code.map(|bytes| bytes.as_bytes().to_vec()),
+            storage: storage.unwrap_or_default(),
+            storage_reset,
+        }
+    }
+}
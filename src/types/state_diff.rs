@@ -0,0 +1,15 @@
+use std::collections::HashMap;
+
+use pyo3::pyclass;
+use revm::primitives::U256;
+
+#[pyclass(get_all)]
+#[derive(Debug, Clone, Default)]
+pub struct AccountDiff {
+    pub balance_before: U256,
+    pub balance_after: U256,
+    pub nonce_before: u64,
+    pub nonce_after: u64,
+    pub code_changed: bool,
+    pub storage: HashMap<U256, (U256, U256)>,
+}
@@ -0,0 +1,13 @@
+use pyo3::pyclass;
+
+#[pyclass(get_all)]
+#[derive(Debug, Clone, Default)]
+pub struct TraceStep {
+    pub pc: u64,
+    pub opcode: u8,
+    pub opcode_name: String,
+    pub gas: u64,
+    pub gas_cost: u64,
+    pub depth: u64,
+    pub stack: Vec<String>,
+}